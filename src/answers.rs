@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::solver::{Answer, Part};
+
+/// Expected answers keyed by day and part, read from a TOML file such as:
+///
+/// ```toml
+/// [day_01]
+/// part_1 = 54390
+/// part_2 = "54277"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    #[must_use]
+    pub fn check(&self, day: u8, part: Part, answer: Answer) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+// PARSING //
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| format!("Invalid key {key:?}, expected {prefix}<N>"))
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse().map_err(|e| format!("{e}"))?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = parse_key(&day_key, "day_")?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected a table for [{day_key}]"))?;
+            for (part_key, value) in parts {
+                let part = Part::try_from(parse_key(part_key, "part_")?)
+                    .map_err(|part| format!("Invalid part {part} in [{day_key}]"))?;
+                let answer = match value {
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    toml::Value::String(answer) => answer.clone(),
+                    _ => return Err(format!("Invalid answer for {day_key}.{part_key}")),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Self(answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[day_01]\npart_1 = 54_390\npart_2 = \"-12\"\n\n[day_9]\npart_1 = 7\n";
+
+    #[test]
+    fn parse() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(Some("54390"), answers.get(1, Part::One));
+        assert_eq!(Some("-12"), answers.get(1, Part::Two));
+        assert_eq!(Some("7"), answers.get(9, Part::One));
+        assert_eq!(None, answers.get(9, Part::Two));
+        assert!("[day_01]\npart_3 = 1".parse::<Answers>().is_err());
+        assert!("[first]\npart_1 = 1".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn check() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::One, Answer::Unsigned(54390))
+        );
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::Two, Answer::Signed(-12))
+        );
+        assert_eq!(
+            Verdict::Fail {
+                expected: "7".to_string()
+            },
+            answers.check(9, Part::One, Answer::Signed(8))
+        );
+        assert_eq!(
+            Verdict::Missing,
+            answers.check(9, Part::Two, Answer::Signed(8))
+        );
+    }
+
+    #[test]
+    fn answers_file() {
+        let answers: Answers = include_str!("../answers.toml").parse().unwrap();
+        assert_eq!(Some("54390"), answers.get(1, Part::One));
+        assert_eq!(Some("127517902575337"), answers.get(19, Part::Two));
+    }
+}
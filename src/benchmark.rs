@@ -0,0 +1,394 @@
+use std::fmt::{self, Write};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solver::{Part, SolveError, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub warm_up: usize,
+    pub iterations: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", with = "nanos")]
+    pub max: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse: Option<Timing>,
+    pub part_1: Timing,
+    pub part_2: Timing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub warm_up: usize,
+    pub iterations: usize,
+    pub days: Vec<DayReport>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+/// The change of one day's stage between a baseline and a later run, by median.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regressed: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            warm_up: 3,
+            iterations: 10,
+        }
+    }
+}
+
+/// Runs `f` for the warm-up rounds, then times each of the measured iterations.
+pub fn measure<T>(settings: Settings, mut f: impl FnMut() -> T) -> Timing {
+    assert!(settings.iterations > 0, "At least one iteration is needed");
+    for _ in 0..settings.warm_up {
+        black_box(f());
+    }
+    let mut samples: Vec<_> = (0..settings.iterations)
+        .map(|_| {
+            let now = Instant::now();
+            black_box(f());
+            now.elapsed()
+        })
+        .collect();
+    samples.sort_unstable();
+    Timing {
+        min: samples[0],
+        median: samples[samples.len() / 2],
+        max: samples[samples.len() - 1],
+    }
+}
+
+/// Times both parts of a day, after checking that the input can be solved. Days whose parts
+/// share a parsing step have it timed on its own, and their parts timed on the parsed input.
+pub fn day(solver: &dyn Solver, input: &str, settings: Settings) -> Result<DayReport, SolveError> {
+    for part in Part::ALL {
+        solver.solve(part, input)?;
+    }
+    let report = match solver.parse(input) {
+        Some(parsed) => {
+            let parsed = parsed?;
+            DayReport {
+                day: solver.day(),
+                parse: Some(measure(settings, || solver.parse(input))),
+                part_1: measure(settings, || parsed.solve(Part::One)),
+                part_2: measure(settings, || parsed.solve(Part::Two)),
+            }
+        }
+        None => DayReport {
+            day: solver.day(),
+            parse: None,
+            part_1: measure(settings, || solver.part_1(input)),
+            part_2: measure(settings, || solver.part_2(input)),
+        },
+    };
+    Ok(report)
+}
+
+impl DayReport {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.parse.map_or(Duration::ZERO, |parse| parse.median)
+            + self.part_1.median
+            + self.part_2.median
+    }
+
+    #[must_use]
+    pub const fn stage(&self, stage: Stage) -> Option<Timing> {
+        match stage {
+            Stage::Parse => self.parse,
+            Stage::Part(Part::One) => Some(self.part_1),
+            Stage::Part(Part::Two) => Some(self.part_2),
+        }
+    }
+}
+
+impl Stage {
+    pub const ALL: [Self; 3] = [Self::Parse, Self::Part(Part::One), Self::Part(Part::Two)];
+}
+
+impl Comparison {
+    /// The relative change of the median, e.g. `0.1` for 10% slower.
+    #[must_use]
+    pub fn change(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+/// Compares every day and stage present in both reports, flagging those whose median grew
+/// by more than `threshold` (e.g. `0.1` for 10%).
+#[must_use]
+pub fn compare(baseline: &Report, current: &Report, threshold: f64) -> Vec<Comparison> {
+    current
+        .days
+        .iter()
+        .filter_map(|day| {
+            let before = baseline.days.iter().find(|before| before.day == day.day)?;
+            Some((before, day))
+        })
+        .flat_map(|(before, day)| {
+            Stage::ALL.into_iter().filter_map(move |stage| {
+                let baseline = before.stage(stage)?.median;
+                let current = day.stage(stage)?.median;
+                let regressed = current.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold);
+                Some(Comparison {
+                    day: day.day,
+                    stage,
+                    baseline,
+                    current,
+                    regressed,
+                })
+            })
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn comparison_to_markdown(comparisons: &[Comparison]) -> String {
+    let mut table = String::from(
+        "| Day | Stage | Baseline | Current | Change | |\n|----:|:------|---------:|--------:|-------:|:-|\n",
+    );
+    for comparison in comparisons {
+        writeln!(
+            table,
+            "| {} | {} | {:.1?} | {:.1?} | {:+.1}% | {} |",
+            comparison.day,
+            comparison.stage,
+            comparison.baseline,
+            comparison.current,
+            comparison.change() * 100.0,
+            if comparison.regressed { "slower" } else { "" }
+        )
+        .unwrap();
+    }
+    table
+}
+
+impl Report {
+    #[must_use]
+    pub const fn new(settings: Settings, days: Vec<DayReport>) -> Self {
+        Self {
+            warm_up: settings.warm_up,
+            iterations: settings.iterations,
+            days,
+        }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.days.iter().map(DayReport::total).sum()
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "| Day | Parse | Part 1 | Part 2 | Total |\n\
+             |----:|------:|-------:|-------:|------:|\n",
+        );
+        for day in &self.days {
+            let parse = day.parse.map_or_else(|| "-".to_string(), format_timing);
+            writeln!(
+                table,
+                "| {} | {parse} | {} | {} | {:.1?} |",
+                day.day,
+                format_timing(day.part_1),
+                format_timing(day.part_2),
+                day.total()
+            )
+            .unwrap();
+        }
+        let stage_total = |stage| -> Duration {
+            self.days
+                .iter()
+                .filter_map(|day| day.stage(stage))
+                .map(|timing| timing.median)
+                .sum()
+        };
+        writeln!(
+            table,
+            "| **Total** | {:.1?} | {:.1?} | {:.1?} | {:.1?} |",
+            stage_total(Stage::Parse),
+            stage_total(Stage::Part(Part::One)),
+            stage_total(Stage::Part(Part::Two)),
+            self.total()
+        )
+        .unwrap();
+        writeln!(
+            table,
+            "\nMedian (min – max) of {} iterations after {} warm-up runs. \
+             Days whose parts share a parser are parsed once and both parts are timed on the \
+             result; the others show - for parse and each part includes its own parsing.",
+            self.iterations, self.warm_up
+        )
+        .unwrap();
+        table
+    }
+}
+
+fn format_timing(timing: Timing) -> String {
+    format!(
+        "{:.1?} ({:.1?} – {:.1?})",
+        timing.median, timing.min, timing.max
+    )
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    fn timing(micros: u64) -> Timing {
+        Timing {
+            min: Duration::from_micros(micros - 1),
+            median: Duration::from_micros(micros),
+            max: Duration::from_micros(micros + 2),
+        }
+    }
+
+    fn report() -> Report {
+        Report::new(
+            Settings::default(),
+            vec![DayReport {
+                day: 3,
+                parse: None,
+                part_1: timing(10),
+                part_2: timing(20),
+            }],
+        )
+    }
+
+    #[test]
+    fn measure_runs() {
+        let mut runs = 0;
+        let settings = Settings {
+            warm_up: 2,
+            iterations: 5,
+        };
+        let timing = measure(settings, || runs += 1);
+        assert_eq!(7, runs);
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+    }
+
+    #[test]
+    fn day_report() {
+        let solver = solver::solver(9).unwrap();
+        let input = include_str!("day_09/example.txt");
+        let report = day(solver, input, Settings::default()).unwrap();
+        assert_eq!(9, report.day);
+        assert!(report.parse.is_some());
+        let report = day(
+            solver::solver(6).unwrap(),
+            include_str!("day_06/example.txt"),
+            Settings::default(),
+        )
+        .unwrap();
+        assert!(report.parse.is_none());
+        assert!(day(solver, "0 x", Settings::default()).is_err());
+    }
+
+    #[test]
+    fn markdown() {
+        let mut report = report();
+        report.days.push(DayReport {
+            day: 4,
+            parse: Some(timing(5)),
+            part_1: timing(2),
+            part_2: timing(3),
+        });
+        assert_eq!(
+            "| Day | Parse | Part 1 | Part 2 | Total |\n\
+             |----:|------:|-------:|-------:|------:|\n\
+             | 3 | - | 10.0µs (9.0µs – 12.0µs) | 20.0µs (19.0µs – 22.0µs) | 30.0µs |\n\
+             | 4 | 5.0µs (4.0µs – 7.0µs) | 2.0µs (1.0µs – 4.0µs) | 3.0µs (2.0µs – 5.0µs) | 10.0µs |\n\
+             | **Total** | 5.0µs | 12.0µs | 23.0µs | 40.0µs |\n\
+             \n\
+             Median (min – max) of 10 iterations after 3 warm-up runs. \
+             Days whose parts share a parser are parsed once and both parts are timed on the \
+             result; the others show - for parse and each part includes its own parsing.\n",
+            report.to_markdown()
+        );
+    }
+
+    #[test]
+    fn comparison() {
+        let baseline = report();
+        let mut current = report();
+        current.days[0].part_1.median = Duration::from_micros(12);
+        current.days[0].part_2.median = Duration::from_micros(21);
+        let comparisons = compare(&baseline, &current, 0.1);
+        assert_eq!(2, comparisons.len());
+        assert_eq!(Stage::Part(Part::One), comparisons[0].stage);
+        assert!(comparisons[0].regressed);
+        assert!(!comparisons[1].regressed);
+        assert_eq!(
+            "| Day | Stage | Baseline | Current | Change | |\n\
+             |----:|:------|---------:|--------:|-------:|:-|\n\
+             | 3 | part 1 | 10.0µs | 12.0µs | +20.0% | slower |\n\
+             | 3 | part 2 | 20.0µs | 21.0µs | +5.0% |  |\n",
+            comparison_to_markdown(&comparisons)
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json = report().to_json();
+        assert!(json.contains("\"median_ns\": 10000"));
+        assert_eq!(report(), Report::from_json(&json).unwrap());
+    }
+}
@@ -1,40 +1,66 @@
-pub fn part_1(input: &str) -> u32 {
-    fn parse_calibration_value(input: &str) -> u32 {
+use crate::parse::ParseError;
+
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    fn parse_calibration_value(input: &str) -> Result<u32, ParseError> {
         let mut digits = input.chars().filter_map(|c| c.to_digit(10)).peekable();
-        10 * digits.peek().unwrap() + digits.last().unwrap()
+        let first = *digits
+            .peek()
+            .ok_or_else(|| ParseError::new("a digit", input))?;
+        Ok(10 * first + digits.last().unwrap())
     }
-    input.lines().map(parse_calibration_value).sum()
+    sum_lines(input, parse_calibration_value)
 }
 
-pub fn part_2(input: &str) -> u32 {
-    fn parse_calibration_value(input: &str) -> u32 {
-        10 * first_digit(input) + last_digit(input)
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    fn parse_calibration_value(input: &str) -> Result<u32, ParseError> {
+        let error = || ParseError::new("a digit", input);
+        Ok(10 * first_digit(input).ok_or_else(error)? + last_digit(input).ok_or_else(error)?)
     }
-    input.lines().map(parse_calibration_value).sum()
+    sum_lines(input, parse_calibration_value)
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap()
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> u32 {
+    try_part_2(input).unwrap()
 }
 
-fn first_digit(mut input: &str) -> u32 {
-    loop {
+fn sum_lines(input: &str, f: impl Fn(&str) -> Result<u32, ParseError>) -> Result<u32, ParseError> {
+    input
+        .lines()
+        .map(f)
+        .sum::<Result<_, _>>()
+        .map_err(|e| e.locate(input))
+}
+
+fn first_digit(mut input: &str) -> Option<u32> {
+    while !input.is_empty() {
         if let Some(digit) = input.chars().next().and_then(|c| c.to_digit(10)) {
-            return digit;
+            return Some(digit);
         }
         if let Some(digit) = find_spelled_digit(|spelled| input.starts_with(spelled)) {
-            return digit;
+            return Some(digit);
         }
         input = &input[1..];
     }
+    None
 }
 
-fn last_digit(mut input: &str) -> u32 {
-    loop {
+fn last_digit(mut input: &str) -> Option<u32> {
+    while !input.is_empty() {
         if let Some(digit) = input.chars().last().and_then(|c| c.to_digit(10)) {
-            return digit;
+            return Some(digit);
         }
         if let Some(digit) = find_spelled_digit(|spelled| input.ends_with(spelled)) {
-            return digit;
+            return Some(digit);
         }
         input = &input[..(input.len() - 1)];
     }
+    None
 }
 
 fn find_spelled_digit(predicate: impl Fn(&str) -> bool) -> Option<u32> {
@@ -50,15 +76,14 @@ const SPELLED_DIGITS: [&str; 9] = [
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE_1: &str = include_str!("example_1.txt");
     const EXAMPLE_2: &str = include_str!("example_2.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -67,7 +92,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(54390, part_1(INPUT));
+        if let Some(input) = input::puzzle(1) {
+            assert_eq!(54390, part_1(&input));
+        }
     }
 
     #[test]
@@ -77,11 +104,26 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(54277, part_2(INPUT));
+        if let Some(input) = input::puzzle(1) {
+            assert_eq!(54277, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn missing_digit() {
+        let error = try_part_1("a1b\nxyz\n").unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected a digit, found \"xyz\"",
+            error.to_string()
+        );
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(1) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug)]
 pub struct Game {
     pub id: u32,
@@ -36,32 +38,32 @@ impl CubeSet {
 }
 
 impl FromStr for Game {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let (id, sets) = s.split_once(':').unwrap();
-        let id = id.strip_prefix("Game ").unwrap().parse().unwrap();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (id, sets) = parse::split_once(s, ":")?;
+        let id = parse::number(parse::strip_prefix(id, "Game ")?)?;
         let sets = sets
             .split(';')
             .map(CubeSet::from_str)
-            .collect::<Result<_, !>>()?;
+            .collect::<Result<_, _>>()?;
         Ok(Self { id, sets })
     }
 }
 
 impl FromStr for CubeSet {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let mut result = Self::default();
         for part in s.trim_start().split(", ") {
-            let (amount, color) = part.split_once(' ').unwrap();
-            let amount = amount.parse().unwrap();
+            let (amount, color) = parse::split_once(part, " ")?;
+            let amount = parse::number(amount)?;
             match color {
                 "red" => result.red = amount,
                 "green" => result.green = amount,
                 "blue" => result.blue = amount,
-                _ => panic!("Unknown color: {color}"),
+                _ => return Err(ParseError::new("red, green or blue", color)),
             }
         }
         Ok(result)
@@ -1,44 +1,61 @@
 use game::{CubeSet, Game};
 
+use crate::parse::ParseError;
+
 mod game;
 
-fn parse_games(input: &str) -> impl Iterator<Item = Game> + '_ {
-    input.lines().map(|line| {
-        let Ok(game) = line.parse();
-        game
-    })
+pub(crate) fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(input))
 }
 
-#[must_use]
-pub fn part_1(input: &str) -> u32 {
+pub(crate) fn solve_1(games: &[Game]) -> u32 {
     let bag = CubeSet {
         red: 12,
         green: 13,
         blue: 14,
     };
-    parse_games(input)
+    games
+        .iter()
         .filter(|game| game.sets.iter().all(|set| bag.contains(set)))
         .map(|game| game.id)
         .sum()
 }
 
+pub(crate) fn solve_2(games: &[Game]) -> u32 {
+    games.iter().map(|game| game.minimum_set().power()).sum()
+}
+
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap()
+}
+
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    parse_games(input)
-        .map(|game| game.minimum_set().power())
-        .sum()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -47,7 +64,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(2512, part_1(INPUT));
+        if let Some(input) = input::puzzle(2) {
+            assert_eq!(2512, part_1(&input));
+        }
     }
 
     #[test]
@@ -57,11 +76,17 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(67335, part_2(INPUT));
+        if let Some(input) = input::puzzle(2) {
+            assert_eq!(67335, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(2) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
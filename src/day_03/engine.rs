@@ -3,8 +3,11 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use crate::parse::ParseError;
+use crate::util::{Matrix, Position};
+
 pub struct Schematic {
-    values: Vec<Vec<Value>>,
+    values: Matrix<Value>,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -18,41 +21,31 @@ enum Value {
 impl Schematic {
     pub fn part_numbers(&self) -> impl Iterator<Item = u32> + '_ {
         self.values
-            .iter()
             .enumerate()
-            .flat_map(move |(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter(|(_, value)| value.is_symbol())
-                    .flat_map(move |(j, _)| self.adjacent_numbers(i, j))
-            })
+            .filter(|(_, value)| value.is_symbol())
+            .flat_map(|(position, _)| self.adjacent_numbers(position))
             .dedup_by(Rc::ptr_eq)
             .map(|n| *n)
     }
 
     pub fn gear_ratios(&self) -> impl Iterator<Item = u32> + '_ {
-        self.values.iter().enumerate().flat_map(move |(i, row)| {
-            row.iter()
-                .enumerate()
-                .filter(move |&(_, value)| value == &Value::Gear)
-                .filter_map(move |(j, _)| {
-                    let numbers: Vec<_> = self.adjacent_numbers(i, j).collect();
-                    if numbers.len() == 2 {
-                        Some(*numbers[0] * *numbers[1])
-                    } else {
-                        None
-                    }
-                })
-        })
+        self.values
+            .enumerate()
+            .filter(|&(_, value)| value == &Value::Gear)
+            .filter_map(|(position, _)| {
+                let numbers: Vec<_> = self.adjacent_numbers(position).collect();
+                if numbers.len() == 2 {
+                    Some(*numbers[0] * *numbers[1])
+                } else {
+                    None
+                }
+            })
     }
 
-    fn adjacent_numbers(&self, i: usize, j: usize) -> impl Iterator<Item = Rc<u32>> + '_ {
-        (i.saturating_sub(1)..=(i + 1))
-            .flat_map(move |i| {
-                let row = self.values.get(i);
-                (j.saturating_sub(1)..=(j + 1)).filter_map(move |j| row.and_then(|row| row.get(j)))
-            })
-            .filter_map(Value::number)
+    fn adjacent_numbers(&self, position: Position) -> impl Iterator<Item = Rc<u32>> + '_ {
+        self.values
+            .neighbours_8(position)
+            .filter_map(|neighbour| self.values[neighbour].number())
             .dedup_by(Rc::ptr_eq)
     }
 }
@@ -72,40 +65,39 @@ impl Value {
 }
 
 impl FromStr for Schematic {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let values = s
-            .lines()
-            .map(|line| {
-                let mut values = Vec::new();
-                let mut number = 0;
-                let mut digit = 0;
-                for c in line.chars() {
-                    if let Some(n) = c.to_digit(10) {
-                        number = 10 * number + n;
-                        digit += 1;
-                    } else {
-                        let value = Rc::new(number);
-                        for _ in 0..digit {
-                            values.push(Value::Number(value.clone()));
-                        }
-                        number = 0;
-                        digit = 0;
-                        values.push(match c {
-                            '.' => Value::Period,
-                            '*' => Value::Gear,
-                            _ => Value::OtherSymbol,
-                        });
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let chars = Matrix::from_str_map(s, Ok)?;
+        let mut values = Vec::with_capacity(chars.height() * chars.width());
+        for row in chars.rows() {
+            let mut number = 0;
+            let mut digit = 0;
+            for &c in row {
+                if let Some(n) = c.to_digit(10) {
+                    number = 10 * number + n;
+                    digit += 1;
+                } else {
+                    let value = Rc::new(number);
+                    for _ in 0..digit {
+                        values.push(Value::Number(value.clone()));
                     }
+                    number = 0;
+                    digit = 0;
+                    values.push(match c {
+                        '.' => Value::Period,
+                        '*' => Value::Gear,
+                        _ => Value::OtherSymbol,
+                    });
                 }
-                let value = Rc::new(number);
-                for _ in 0..digit {
-                    values.push(Value::Number(value.clone()));
-                }
-                values
-            })
-            .collect();
-        Ok(Self { values })
+            }
+            let value = Rc::new(number);
+            for _ in 0..digit {
+                values.push(Value::Number(value.clone()));
+            }
+        }
+        Ok(Self {
+            values: Matrix::from_vec(chars.height(), chars.width(), values),
+        })
     }
 }
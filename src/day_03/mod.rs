@@ -1,29 +1,46 @@
-use std::str::FromStr;
+use crate::parse::{self, ParseError};
 
 mod engine;
 
+pub(crate) fn parse_input(input: &str) -> Result<engine::Schematic, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(schematic: &engine::Schematic) -> u32 {
+    schematic.part_numbers().sum()
+}
+
+pub(crate) fn solve_2(schematic: &engine::Schematic) -> u32 {
+    schematic.gear_ratios().sum()
+}
+
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> u32 {
-    let Ok(schematic) = engine::Schematic::from_str(input);
-    schematic.part_numbers().sum()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    let Ok(schematic) = engine::Schematic::from_str(input);
-    schematic.gear_ratios().sum()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -32,7 +49,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(527_369, part_1(INPUT));
+        if let Some(input) = input::puzzle(3) {
+            assert_eq!(527_369, part_1(&input));
+        }
     }
 
     #[test]
@@ -42,11 +61,17 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(73_074_886, part_2(INPUT));
+        if let Some(input) = input::puzzle(3) {
+            assert_eq!(73_074_886, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(3) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
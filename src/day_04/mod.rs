@@ -1,24 +1,22 @@
 use model::Scratchcard;
 
+use crate::parse::ParseError;
+
 mod model;
 
-fn parse_scratchcards(input: &str) -> impl Iterator<Item = Scratchcard> + '_ {
-    input.lines().map(|line| {
-        let Ok(scratchcard) = line.parse();
-        scratchcard
-    })
+pub(crate) fn parse_input(input: &str) -> Result<Vec<Scratchcard>, ParseError> {
+    input
+        .lines()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(input))
 }
 
-#[must_use]
-pub fn part_1(input: &str) -> u32 {
-    parse_scratchcards(input)
-        .map(|scratchcard| scratchcard.points())
-        .sum()
+pub(crate) fn solve_1(scratchcards: &[Scratchcard]) -> u32 {
+    scratchcards.iter().map(Scratchcard::points).sum()
 }
 
-#[must_use]
-pub fn part_2(input: &str) -> usize {
-    let scratchcards: Vec<_> = parse_scratchcards(input).collect();
+pub(crate) fn solve_2(scratchcards: &[Scratchcard]) -> usize {
     let mut amounts: Vec<usize> = scratchcards.iter().map(|_| 1).collect();
     for (index, scratchcard) in scratchcards.iter().enumerate() {
         let amount = amounts[index];
@@ -31,16 +29,33 @@ pub fn part_2(input: &str) -> usize {
     amounts.into_iter().sum()
 }
 
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap()
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> usize {
+    try_part_2(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -49,7 +64,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(28750, part_1(INPUT));
+        if let Some(input) = input::puzzle(4) {
+            assert_eq!(28750, part_1(&input));
+        }
     }
 
     #[test]
@@ -59,11 +76,17 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(10_212_704, part_2(INPUT));
+        if let Some(input) = input::puzzle(4) {
+            assert_eq!(10_212_704, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(4) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 pub struct Scratchcard {
     winning_numbers: Vec<u32>,
     numbers: Vec<u32>,
@@ -22,18 +24,18 @@ impl Scratchcard {
 }
 
 impl FromStr for Scratchcard {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let (_id, numbers) = s.split_once(':').unwrap();
-        let (winning_numbers, numbers) = numbers.split_once('|').unwrap();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (_id, numbers) = parse::split_once(s, ":")?;
+        let (winning_numbers, numbers) = parse::split_once(numbers, "|")?;
         Ok(Self {
-            winning_numbers: parse_numbers(winning_numbers),
-            numbers: parse_numbers(numbers),
+            winning_numbers: parse_numbers(winning_numbers)?,
+            numbers: parse_numbers(numbers)?,
         })
     }
 }
 
-fn parse_numbers(s: &str) -> Vec<u32> {
-    s.split_whitespace().map(|n| n.parse().unwrap()).collect()
+fn parse_numbers(s: &str) -> Result<Vec<u32>, ParseError> {
+    s.split_whitespace().map(parse::number).collect()
 }
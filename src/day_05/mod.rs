@@ -1,12 +1,15 @@
-use std::str::FromStr;
-
 use model::{Almanac, Range};
 
+use crate::parse::{self, ParseError};
+use crate::util::{self, ParIter as _};
+
 mod model;
 
-#[must_use]
-pub fn part_1(input: &str) -> u64 {
-    let Ok(almanac) = Almanac::from_str(input);
+pub(crate) fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(almanac: &Almanac) -> u64 {
     almanac
         .seeds
         .iter()
@@ -15,28 +18,45 @@ pub fn part_1(input: &str) -> u64 {
         .unwrap()
 }
 
-#[must_use]
-pub fn part_2(input: &str) -> u64 {
-    let Ok(almanac) = Almanac::from_str(input);
-    almanac
+pub(crate) fn solve_2(almanac: &Almanac) -> u64 {
+    let ranges: Vec<_> = almanac
         .seeds
-        .chunks(2)
+        .chunks_exact(2)
         .map(|chunk| Range::new(chunk[0], chunk[0] + chunk[1]))
+        .collect();
+    util::par_iter(ranges)
         .map(|seeds| almanac.closest_location_for_seeds(seeds))
         .min()
         .unwrap()
 }
 
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u64 {
+    try_part_1(input).unwrap()
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> u64 {
+    try_part_2(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -45,7 +65,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(662_197_086, part_1(INPUT));
+        if let Some(input) = input::puzzle(5) {
+            assert_eq!(662_197_086, part_1(&input));
+        }
     }
 
     #[test]
@@ -55,11 +77,25 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(52_510_809, part_2(INPUT));
+        if let Some(input) = input::puzzle(5) {
+            assert_eq!(52_510_809, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn odd_seeds() {
+        let maps = &EXAMPLE[EXAMPLE.find('\n').unwrap()..];
+        let error = try_part_2(&format!("seeds: 79 14 55{maps}")).unwrap_err();
+        assert_eq!("an even number of seeds", error.expected());
+        assert_eq!("seeds: 79 14 55", error.found());
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(5) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -2,6 +2,8 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug)]
 pub struct Almanac {
     pub seeds: Vec<u64>,
@@ -100,7 +102,7 @@ impl Map {
 }
 
 impl MapRange {
-    fn apply(&self, source: u64) -> Option<u64> {
+    const fn apply(&self, source: u64) -> Option<u64> {
         if self.source.includes(source) {
             Some(source.checked_add_signed(self.offset).unwrap())
         } else {
@@ -114,7 +116,7 @@ impl MapRange {
             .map(|overlap| overlap.start.checked_add_signed(self.offset).unwrap())
     }
 
-    fn destination(&self) -> Range {
+    const fn destination(&self) -> Range {
         self.source.transpose(self.offset)
     }
 }
@@ -138,7 +140,7 @@ impl Range {
         self.start <= value && value < self.end
     }
 
-    fn transpose(self, offset: i64) -> Self {
+    const fn transpose(self, offset: i64) -> Self {
         Self {
             start: self.start.checked_add_signed(offset).unwrap(),
             end: self.end.checked_add_signed(offset).unwrap(),
@@ -147,22 +149,23 @@ impl Range {
 }
 
 impl FromStr for Almanac {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let mut parts = s.split("\n\n");
-        let seeds = parts
-            .next()
-            .unwrap()
-            .strip_prefix("seeds: ")
-            .unwrap()
+        let line = parts.next().unwrap();
+        let seeds: Vec<_> = parse::strip_prefix(line, "seeds: ")?
             .split(' ')
-            .map(|n| n.parse().unwrap())
-            .collect();
+            .map(parse::number)
+            .collect::<Result<_, _>>()?;
+        // Part two reads the seeds as pairs of a start and a length.
+        if seeds.len() % 2 != 0 {
+            return Err(ParseError::new("an even number of seeds", line));
+        }
         let seed_to_location_map = parts
-            .map(|s| Map::from_str(s).unwrap())
-            .reduce(|a, b| a.chain(&b))
-            .unwrap();
+            .map(Map::from_str)
+            .reduce(|a, b| Ok(a?.chain(&b?)))
+            .ok_or_else(|| ParseError::new("a map", &s[s.len()..]))??;
         Ok(Self {
             seeds,
             seed_to_location_map,
@@ -171,27 +174,29 @@ impl FromStr for Almanac {
 }
 
 impl FromStr for Map {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let ranges: Vec<MapRange> = s
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let ranges = s
             .lines()
             .skip(1)
-            .map(|line| line.parse().unwrap())
-            .collect();
+            .map(MapRange::from_str)
+            .collect::<Result<_, _>>()?;
         Ok(Self::new(ranges))
     }
 }
 
 impl FromStr for MapRange {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let (destination_start, source_start, length) = s
             .split(' ')
-            .map(|n| n.parse().unwrap())
+            .map(parse::number)
+            .collect::<Result<Vec<u64>, _>>()?
+            .into_iter()
             .collect_tuple()
-            .unwrap();
+            .ok_or_else(|| ParseError::new("three numbers", s))?;
         let source = Range::new(source_start, source_start + length);
         let offset =
             i64::try_from(destination_start).unwrap() - i64::try_from(source_start).unwrap();
@@ -1,6 +1,8 @@
 use std::iter::zip;
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug)]
 pub struct Race {
     time: u64,
@@ -14,57 +16,49 @@ impl Race {
         clippy::cast_precision_loss
     )]
     pub fn number_of_ways_to_beat(&self) -> u64 {
-        let sd = f64::sqrt((self.time * self.time - 4 * self.record_distance) as f64);
+        let Some(discriminant) = (self.time * self.time).checked_sub(4 * self.record_distance)
+        else {
+            return 0;
+        };
+        let sd = f64::sqrt(discriminant as f64);
         let time = self.time as f64;
-        let a = (time + sd) / 2.0;
+        let a = f64::midpoint(time, sd);
         let b = (time - sd) / 2.0;
-        (a.ceil() - b.floor()) as u64 - 1
+        ((a.ceil() - b.floor()) as u64).saturating_sub(1)
     }
 }
 
-pub fn parse_races(input: &str) -> impl Iterator<Item = Race> + '_ {
-    let mut lines = input.lines();
-    let time = lines
-        .next()
-        .unwrap()
-        .strip_prefix("Time:")
-        .unwrap()
-        .split_whitespace();
-    let distance = lines
-        .next()
-        .unwrap()
-        .strip_prefix("Distance:")
-        .unwrap()
-        .split_whitespace();
-    zip(time, distance).map(|(time, distance)| Race {
-        time: time.parse().unwrap(),
-        record_distance: distance.parse().unwrap(),
-    })
+pub fn parse_races(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (time, distance) = parse_lines(input)?;
+    if time.split_whitespace().count() != distance.split_whitespace().count() {
+        return Err(ParseError::new("as many distances as times", distance));
+    }
+    zip(time.split_whitespace(), distance.split_whitespace())
+        .map(|(time, distance)| {
+            Ok(Race {
+                time: parse::number(time)?,
+                record_distance: parse::number(distance)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_lines(s: &str) -> Result<(&str, &str), ParseError> {
+    let mut lines = s.lines();
+    let end = &s[s.len()..];
+    let time = parse::strip_prefix(lines.next().unwrap_or(end), "Time:")?;
+    let distance = parse::strip_prefix(lines.next().unwrap_or(end), "Distance:")?;
+    Ok((time, distance))
 }
 
 impl FromStr for Race {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let s = s.replace(' ', "");
-        let mut lines = s.lines();
-        let time = lines
-            .next()
-            .unwrap()
-            .strip_prefix("Time:")
-            .unwrap()
-            .parse()
-            .unwrap();
-        let record_distance = lines
-            .next()
-            .unwrap()
-            .strip_prefix("Distance:")
-            .unwrap()
-            .parse()
-            .unwrap();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (time, distance) = parse_lines(s)?;
         Ok(Self {
-            time,
-            record_distance,
+            time: parse::number(&time.replace(' ', ""))?,
+            record_distance: parse::number(&distance.replace(' ', ""))?,
         })
     }
 }
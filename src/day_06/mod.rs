@@ -1,30 +1,42 @@
-use std::str::FromStr;
+use crate::parse::{self, ParseError};
 
 mod boat;
 
+pub(crate) fn parse_input(input: &str) -> Result<Vec<boat::Race>, ParseError> {
+    boat::parse_races(input).map_err(|e| e.locate(input))
+}
+
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .map(boat::Race::number_of_ways_to_beat)
+        .product())
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    let race: boat::Race = parse::from_str(input)?;
+    Ok(race.number_of_ways_to_beat())
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> u64 {
-    boat::parse_races(input)
-        .map(|race| race.number_of_ways_to_beat())
-        .product()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    let Ok(race) = boat::Race::from_str(input);
-    race.number_of_ways_to_beat()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -33,7 +45,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(293_046, part_1(INPUT));
+        if let Some(input) = input::puzzle(6) {
+            assert_eq!(293_046, part_1(&input));
+        }
     }
 
     #[test]
@@ -43,11 +57,26 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(35_150_181, part_2(INPUT));
+        if let Some(input) = input::puzzle(6) {
+            assert_eq!(35_150_181, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let error = try_part_1("Time: 7 15 30\nDistance: 9 40").unwrap_err();
+        assert_eq!("as many distances as times", error.expected());
+        assert_eq!(Ok(0), try_part_1("Time: 7\nDistance: 100"));
+        assert_eq!(Ok(0), try_part_2("Time: 7\nDistance: 100"));
+        assert_eq!(Ok(0), try_part_1("Time: 4\nDistance: 4"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(6) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
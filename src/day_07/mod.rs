@@ -1,48 +1,62 @@
 use model::{Bid, Hand};
 
+use crate::parse::{self, ParseError};
+
 mod model;
 
-fn winnings(input: &str, has_jokers: bool) -> u32 {
-    let mut hands: Vec<_> = input
+pub(crate) fn parse_input(input: &str, has_jokers: bool) -> Result<Vec<(Hand, Bid)>, ParseError> {
+    input
         .lines()
         .map(|line| parse_line(line, has_jokers))
-        .collect();
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(input))
+}
+
+fn winnings(input: &str, has_jokers: bool) -> Result<u32, ParseError> {
+    let mut hands = parse_input(input, has_jokers)?;
     hands.sort_by_key(|(hand, _)| *hand);
-    hands
+    Ok(hands
         .into_iter()
         .enumerate()
         .map(|(index, (_, bid))| {
             let rank = u32::try_from(index).unwrap() + 1;
             bid * rank
         })
-        .sum()
+        .sum())
+}
+
+fn parse_line(line: &str, has_jokers: bool) -> Result<(Hand, Bid), ParseError> {
+    let (hand, bid) = parse::split_once(line, " ")?;
+    Ok((Hand::from_str(hand, has_jokers)?, parse::number(bid)?))
+}
+
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    winnings(input, false)
 }
 
-fn parse_line(line: &str, has_jokers: bool) -> (Hand, Bid) {
-    let (hand, bid) = line.split_once(' ').unwrap();
-    (Hand::from_str(hand, has_jokers), bid.parse().unwrap())
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    winnings(input, true)
 }
 
 #[must_use]
 pub fn part_1(input: &str) -> u32 {
-    winnings(input, false)
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u32 {
-    winnings(input, true)
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -51,7 +65,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(248_559_379, part_1(INPUT));
+        if let Some(input) = input::puzzle(7) {
+            assert_eq!(248_559_379, part_1(&input));
+        }
     }
 
     #[test]
@@ -61,11 +77,17 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(249_631_254, part_2(INPUT));
+        if let Some(input) = input::puzzle(7) {
+            assert_eq!(249_631_254, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(7) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
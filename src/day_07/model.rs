@@ -2,6 +2,8 @@ use std::cmp::Reverse;
 
 use itertools::Itertools;
 
+use crate::parse::{self, ParseError};
+
 pub type Bid = u32;
 
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash)]
@@ -59,27 +61,26 @@ impl Hand {
         }
     }
 
-    pub fn from_str(s: &str, has_jokers: bool) -> Self {
-        let cards = s
-            .chars()
-            .map(|c| Card::from_char(c, has_jokers))
-            .collect_vec()
+    pub fn from_str(s: &str, has_jokers: bool) -> Result<Self, ParseError> {
+        let cards = parse::chars(s, |c| Card::from_char(c, has_jokers))
+            .collect::<Result<Vec<_>, _>>()?
             .try_into()
-            .unwrap();
-        Self::new(cards)
+            .map_err(|_| ParseError::new("five cards", s))?;
+        Ok(Self::new(cards))
     }
 }
 
 impl Card {
-    fn from_char(c: char, has_jokers: bool) -> Self {
-        match c {
+    fn from_char(c: char, has_jokers: bool) -> Result<Self, ParseError> {
+        Ok(match c {
             'A' => Self::A,
             'K' => Self::K,
             'Q' => Self::Q,
             'J' if has_jokers => Self::Joker,
             'J' => Self::J,
             'T' => Self::T,
-            _ => Self::Number(u8::try_from(c.to_digit(10).unwrap()).unwrap()),
-        }
+            '2'..='9' => Self::Number(u8::try_from(c.to_digit(10).unwrap()).unwrap()),
+            _ => return Err(ParseError::char("a card", c)),
+        })
     }
 }
@@ -1,44 +1,69 @@
-use std::str::FromStr;
+use model::{Map, NodeIterator};
 
-use itertools::Itertools;
-
-use model::Map;
+use crate::parse::{self, ParseError};
+use crate::solver::SolveError;
 
 mod math;
 mod model;
 
+pub(crate) fn parse_input(input: &str) -> Result<Map, ParseError> {
+    parse::from_str(input)
+}
+
+/// The number of steps until the walk reaches an end node, if it ever does.
+fn steps_to_end(map: &Map, walk: NodeIterator<'_>, is_end: impl Fn(&str) -> bool) -> Option<usize> {
+    walk.take(map.walk_limit())
+        .position(is_end)
+        .map(|steps| steps + 1)
+}
+
+pub(crate) fn solve_1(map: &Map) -> Result<usize, SolveError> {
+    if !map.contains("AAA") {
+        return Err(SolveError::NoAnswer("there is no node labelled AAA"));
+    }
+    steps_to_end(map, map.get("AAA"), |node| node == "ZZZ")
+        .ok_or(SolveError::NoAnswer("ZZZ cannot be reached from AAA"))
+}
+
+pub(crate) fn solve_2(map: &Map) -> Result<usize, SolveError> {
+    map.ghosts()
+        .map(|ghost| {
+            steps_to_end(map, ghost, |node| node.ends_with('Z')).ok_or(SolveError::NoAnswer(
+                "a ghost never reaches a node ending in Z",
+            ))
+        })
+        .reduce(|a, b| Ok(math::lcm(a?, b?)))
+        .unwrap_or(Err(SolveError::NoAnswer("no node ends in A")))
+}
+
+pub fn try_part_1(input: &str) -> Result<usize, SolveError> {
+    solve_1(&parse_input(input)?)
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, SolveError> {
+    solve_2(&parse_input(input)?)
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    let Ok(map) = Map::from_str(input);
-    map.get("AAA")
-        .take_while_inclusive(|&node| node != "ZZZ")
-        .count()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let Ok(map) = Map::from_str(input);
-    map.ghosts()
-        .map(|ghost| {
-            ghost
-                .take_while_inclusive(|node| !node.ends_with('Z'))
-                .count()
-        })
-        .reduce(math::lcm)
-        .unwrap()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE_1: &str = include_str!("example_1.txt");
     const EXAMPLE_2: &str = include_str!("example_2.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -47,7 +72,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(20513, part_1(INPUT));
+        if let Some(input) = input::puzzle(8) {
+            assert_eq!(20513, part_1(&input));
+        }
     }
 
     #[test]
@@ -57,11 +84,49 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(15_995_167_053_923, part_2(INPUT));
+        if let Some(input) = input::puzzle(8) {
+            assert_eq!(15_995_167_053_923, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        let error = parse_input("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, Z").unwrap_err();
+        assert_eq!("\")\"", error.expected());
+        let error = parse_input("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZ)").unwrap_err();
+        assert_eq!("a node in the network", error.expected());
+        assert_eq!("ZZZ", error.found());
+        assert!(try_part_1("L\n\nBBB = (BBB, BBB)").is_err());
+        assert!(try_part_2("L\n\nBBB = (BBB, BBB)").is_err());
+    }
+
+    #[test]
+    fn unreachable() {
+        let no_answer = |reason| Err(SolveError::NoAnswer(reason));
+        let input = "L\n\nAAA = (AAA, AAA)";
+        assert_eq!(
+            no_answer("ZZZ cannot be reached from AAA"),
+            try_part_1(input)
+        );
+        assert_eq!(
+            no_answer("a ghost never reaches a node ending in Z"),
+            try_part_2(input)
+        );
+        // ZZZ is only left of AAA, but the walk always arrives at AAA on an R.
+        let input = "RL\n\nAAA = (ZZZ, BBB)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            no_answer("ZZZ cannot be reached from AAA"),
+            try_part_1(input)
+        );
+        assert_eq!(Ok(1), try_part_1(&input.replacen("RL", "LR", 1)));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(8) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
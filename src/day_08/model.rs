@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::iter::Cycle;
 use std::slice::Iter;
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug, Clone)]
 pub struct Map {
     instructions: Vec<Instruction>,
@@ -29,11 +31,20 @@ pub struct NodeIterator<'a> {
 }
 
 impl Map {
+    pub fn contains(&self, node: &str) -> bool {
+        self.network.contains_key(node)
+    }
+
+    /// A walk that has not reached an end node after this many steps is in a loop without one.
+    pub fn walk_limit(&self) -> usize {
+        self.network.len() * self.instructions.len()
+    }
+
     pub fn get<'a>(&'a self, node: &'a str) -> NodeIterator<'a> {
         NodeIterator::new(self, node)
     }
 
-    pub fn ghosts(&self) -> impl Iterator<Item = NodeIterator> {
+    pub fn ghosts(&self) -> impl Iterator<Item = NodeIterator<'_>> {
         self.network
             .keys()
             .filter(|node| node.ends_with('A'))
@@ -42,11 +53,11 @@ impl Map {
 }
 
 impl Instruction {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Result<Self, ParseError> {
         match c {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            _ => unreachable!(),
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            _ => Err(ParseError::char("L or R", c)),
         }
     }
 }
@@ -76,20 +87,37 @@ impl<'a> Iterator for NodeIterator<'a> {
 }
 
 impl FromStr for Map {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         let mut lines = s.lines();
-        let instructions = lines
-            .next()
-            .unwrap()
-            .chars()
-            .map(Instruction::from_char)
-            .collect();
-        let network = lines
+        let instructions = parse::chars(lines.next().unwrap_or(s), Instruction::from_char)
+            .collect::<Result<_, _>>()?;
+        let nodes = lines
             .skip(1)
-            .map(|line| Node::from_str(line).unwrap())
-            .map(|node| (node.label.clone(), node))
+            .map(Node::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        let labels = nodes
+            .iter()
+            .map(|&(label, _, _)| label)
+            .collect::<HashSet<_>>();
+        if let Some(&missing) = nodes
+            .iter()
+            .flat_map(|(_, left, right)| [left, right])
+            .find(|next| !labels.contains(*next))
+        {
+            return Err(ParseError::new("a node in the network", missing));
+        }
+        let network = nodes
+            .into_iter()
+            .map(|(label, left, right)| {
+                let node = Node {
+                    label: label.to_string(),
+                    left: left.to_string(),
+                    right: right.to_string(),
+                };
+                (node.label.clone(), node)
+            })
             .collect();
         Ok(Self {
             instructions,
@@ -98,22 +126,12 @@ impl FromStr for Map {
     }
 }
 
-impl FromStr for Node {
-    type Err = !;
-
-    fn from_str(s: &str) -> Result<Self, !> {
-        let (label, node) = s.split_once(" = ").unwrap();
-        let (left, right) = node
-            .strip_prefix('(')
-            .unwrap()
-            .strip_suffix(')')
-            .unwrap()
-            .split_once(", ")
-            .unwrap();
-        Ok(Self {
-            label: label.to_string(),
-            left: left.to_string(),
-            right: right.to_string(),
-        })
+impl Node {
+    /// Splits a line into the node's label and the labels of its left and right neighbours.
+    fn parse(s: &str) -> Result<(&str, &str, &str), ParseError> {
+        let (label, node) = parse::split_once(s, " = ")?;
+        let node = parse::strip_suffix(parse::strip_prefix(node, "(")?, ")")?;
+        let (left, right) = parse::split_once(node, ", ")?;
+        Ok((label, left, right))
     }
 }
@@ -1,29 +1,54 @@
+use crate::parse::ParseError;
+
 mod oasis;
 
-fn parse_input(input: &str) -> impl Iterator<Item = oasis::History> + '_ {
-    input.lines().map(|line| line.parse().unwrap())
+pub(crate) fn parse_input(input: &str) -> Result<Vec<oasis::History>, ParseError> {
+    input
+        .lines()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(input))
+}
+
+pub(crate) fn solve_1(histories: &[oasis::History]) -> i32 {
+    histories.iter().cloned().map(oasis::History::next).sum()
+}
+
+pub(crate) fn solve_2(histories: &[oasis::History]) -> i32 {
+    histories
+        .iter()
+        .cloned()
+        .map(oasis::History::previous)
+        .sum()
+}
+
+pub fn try_part_1(input: &str) -> Result<i32, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<i32, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
 }
 
 #[must_use]
 pub fn part_1(input: &str) -> i32 {
-    parse_input(input).map(oasis::History::next).sum()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> i32 {
-    parse_input(input).map(oasis::History::previous).sum()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -32,7 +57,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(1_995_001_648, part_1(INPUT));
+        if let Some(input) = input::puzzle(9) {
+            assert_eq!(1_995_001_648, part_1(&input));
+        }
     }
 
     #[test]
@@ -42,11 +69,24 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(988, part_2(INPUT));
+        if let Some(input) = input::puzzle(9) {
+            assert_eq!(988, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn short_histories() {
+        assert_eq!(5, part_1("1 3"));
+        assert_eq!(-1, part_2("1 3"));
+        assert_eq!(7, part_2("7"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(9) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
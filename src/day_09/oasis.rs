@@ -1,5 +1,8 @@
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
+#[derive(Clone)]
 pub struct History {
     values: Vec<i32>,
 }
@@ -24,7 +27,7 @@ impl History {
                 self.values[index] = self.values[index + 1] - self.values[index];
             }
             self.values.pop();
-            previous += self.values[0] * modifier;
+            previous += self.values.first().map_or(0, |&first| first * modifier);
             modifier *= -1;
         }
         previous
@@ -32,10 +35,10 @@ impl History {
 }
 
 impl FromStr for History {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let values = s.split(' ').map(|s| s.parse().unwrap()).collect();
+        let values = s.split(' ').map(parse::number).collect::<Result<_, _>>()?;
         Ok(Self { values })
     }
 }
@@ -1,33 +1,50 @@
 mod model;
-mod nest;
 
+use crate::parse::{self, ParseError};
+use crate::util::{Polygon, Vector2D};
 use model::Sketch;
-use std::str::FromStr;
+
+pub(crate) fn parse_input(input: &str) -> Result<Sketch, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(sketch: &Sketch) -> usize {
+    sketch.pipe().count().div_ceil(2)
+}
+
+pub(crate) fn solve_2(sketch: &Sketch) -> usize {
+    let pipe = Polygon::new(sketch.pipe().map(Vector2D::from).collect());
+    usize::try_from(pipe.interior_points()).unwrap()
+}
+
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
 
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    let Ok(sketch) = Sketch::from_str(input);
-    sketch.pipe().count().div_ceil(2)
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let Ok(sketch) = Sketch::from_str(input);
-    let finder = nest::Finder::new(sketch.width(), sketch.height(), sketch.pipe());
-    finder.inside_area()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE_1: &str = include_str!("example_1.txt");
     const EXAMPLE_2: &str = include_str!("example_2.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -36,7 +53,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(6909, part_1(INPUT));
+        if let Some(input) = input::puzzle(10) {
+            assert_eq!(6909, part_1(&input));
+        }
     }
 
     #[test]
@@ -46,11 +65,24 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(461, part_2(INPUT));
+        if let Some(input) = input::puzzle(10) {
+            assert_eq!(461, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn unconnected_start() {
+        let error = parse_input("S.\n..").unwrap_err();
+        assert_eq!("a pipe connected to the start", error.expected());
+        assert_eq!("S", error.found());
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(10) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
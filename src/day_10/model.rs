@@ -1,15 +1,13 @@
 use std::str::FromStr;
 
-pub const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::East,
-    Direction::South,
-    Direction::West,
-];
+use crate::parse::ParseError;
+use crate::util::{Direction, Matrix, Position};
 
 #[derive(Clone, Debug)]
 pub struct Sketch {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Matrix<Tile>,
+    start: Position,
+    direction: Direction,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -19,139 +17,64 @@ enum Tile {
     Start,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
 pub struct PipeIterator<'a> {
     sketch: &'a Sketch,
-    position: (usize, usize),
+    position: Position,
     direction: Direction,
     started: bool,
 }
 
 impl Sketch {
-    fn get(&self, position: (usize, usize)) -> Option<&Tile> {
-        let (row, column) = position;
-        self.tiles.get(row).and_then(|row| row.get(column))
-    }
-
-    pub fn width(&self) -> usize {
-        self.tiles[0].len()
-    }
-
-    pub fn height(&self) -> usize {
-        self.tiles.len()
-    }
-
-    pub fn pipe(&self) -> PipeIterator<'_> {
-        let start = self.start_position();
-        let direction = DIRECTIONS
-            .iter()
-            .find_map(|&direction| {
-                if let Tile::Pipe { directions } = self.get(direction.apply(start)?)?
-                    && directions.contains(&direction.opposite())
-                {
-                    Some(direction)
-                } else {
-                    None
-                }
-            })
-            .unwrap();
+    pub const fn pipe(&self) -> PipeIterator<'_> {
         PipeIterator {
             sketch: self,
-            position: start,
-            direction,
+            position: self.start,
+            direction: self.direction,
             started: false,
         }
     }
-
-    fn start_position(&self) -> (usize, usize) {
-        self.tiles
-            .iter()
-            .enumerate()
-            .find_map(|(row, tiles)| {
-                tiles.iter().enumerate().find_map(|(col, tile)| {
-                    if tile == &Tile::Start {
-                        Some((row, col))
-                    } else {
-                        None
-                    }
-                })
-            })
-            .unwrap()
-    }
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
-        match c {
+impl TryFrom<char> for Tile {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
+        Ok(match c {
             '|' => Self::Pipe {
-                directions: [Direction::North, Direction::South],
+                directions: [Direction::Up, Direction::Down],
             },
             '-' => Self::Pipe {
-                directions: [Direction::East, Direction::West],
+                directions: [Direction::Right, Direction::Left],
             },
             'L' => Self::Pipe {
-                directions: [Direction::North, Direction::East],
+                directions: [Direction::Up, Direction::Right],
             },
             'J' => Self::Pipe {
-                directions: [Direction::North, Direction::West],
+                directions: [Direction::Up, Direction::Left],
             },
             '7' => Self::Pipe {
-                directions: [Direction::South, Direction::West],
+                directions: [Direction::Down, Direction::Left],
             },
             'F' => Self::Pipe {
-                directions: [Direction::South, Direction::East],
+                directions: [Direction::Down, Direction::Right],
             },
             '.' => Self::Ground,
             'S' => Self::Start,
-            _ => panic!("Invalid character"),
-        }
-    }
-}
-
-impl Direction {
-    pub fn apply(self, position: (usize, usize)) -> Option<(usize, usize)> {
-        let (row, column) = position;
-        Some(match self {
-            Self::North => (row.checked_sub(1)?, column),
-            Self::East => (row, column + 1),
-            Self::South => (row + 1, column),
-            Self::West => (row, column.checked_sub(1)?),
+            _ => return Err(ParseError::char("a pipe, ground or start tile", c)),
         })
     }
-
-    const fn opposite(self) -> Self {
-        match self {
-            Self::North => Self::South,
-            Self::East => Self::West,
-            Self::South => Self::North,
-            Self::West => Self::East,
-        }
-    }
-}
-
-impl PipeIterator<'_> {
-    pub const fn direction(&self) -> Direction {
-        self.direction
-    }
 }
 
 impl Iterator for PipeIterator<'_> {
-    type Item = (usize, usize);
+    type Item = Position;
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.started {
             self.started = true;
             return Some(self.position);
         }
-        self.position = self.direction.apply(self.position)?;
-        match self.sketch.get(self.position)? {
+        self.position = self.position.step(self.direction)?;
+        match self.sketch.tiles.get(self.position)? {
             Tile::Pipe { directions } => {
                 self.direction = directions
                     .iter()
@@ -165,13 +88,35 @@ impl Iterator for PipeIterator<'_> {
 }
 
 impl FromStr for Sketch {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let tiles = s
-            .lines()
-            .map(|line| line.chars().map(Tile::from).collect())
-            .collect();
-        Ok(Self { tiles })
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let tiles: Matrix<Tile> = s.parse()?;
+        let Some(start) = tiles
+            .enumerate()
+            .find_map(|(position, &tile)| (tile == Tile::Start).then_some(position))
+        else {
+            return Err(ParseError::new("a start tile", &s[s.len()..]));
+        };
+        let connects = |direction: Direction| {
+            start
+                .step(direction)
+                .and_then(|position| tiles.get(position))
+                .is_some_and(|tile| match tile {
+                    Tile::Pipe { directions } => directions.contains(&direction.opposite()),
+                    _ => false,
+                })
+        };
+        let Some(direction) = Direction::ALL.into_iter().find(|&d| connects(d)) else {
+            let found = s
+                .find('S')
+                .map_or_else(|| &s[s.len()..], |offset| &s[offset..=offset]);
+            return Err(ParseError::new("a pipe connected to the start", found));
+        };
+        Ok(Self {
+            tiles,
+            start,
+            direction,
+        })
     }
 }
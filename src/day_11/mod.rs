@@ -1,30 +1,47 @@
 mod model;
 
+use crate::parse::{self, ParseError};
 use model::Image;
-use std::str::FromStr;
+
+pub(crate) fn parse_input(input: &str) -> Result<Image, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(image: &Image) -> u64 {
+    image.galaxy_distances(2)
+}
+
+pub(crate) fn solve_2(image: &Image) -> u64 {
+    image.galaxy_distances(1_000_000)
+}
+
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
 
 #[must_use]
 pub fn part_1(input: &str) -> u64 {
-    let Ok(image) = Image::from_str(input);
-    image.galaxy_distances(2)
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    let Ok(image) = Image::from_str(input);
-    image.galaxy_distances(1_000_000)
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -33,23 +50,37 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(9_329_143, part_1(INPUT));
+        if let Some(input) = input::puzzle(11) {
+            assert_eq!(9_329_143, part_1(&input));
+        }
     }
 
     #[test]
     fn example_2() {
-        let Ok(image) = Image::from_str(EXAMPLE);
+        let image: Image = parse::from_str(EXAMPLE).unwrap();
         assert_eq!(1030, image.galaxy_distances(10));
         assert_eq!(8410, image.galaxy_distances(100));
     }
 
     #[test]
     fn answer_2() {
-        assert_eq!(710_674_907_809, part_2(INPUT));
+        if let Some(input) = input::puzzle(11) {
+            assert_eq!(710_674_907_809, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn no_galaxies() {
+        assert_eq!(0, part_1("..\n.."));
+        assert_eq!(0, part_2("..\n.#"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(11) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
+use crate::parse::ParseError;
+use crate::util::Matrix;
+
 pub struct Image {
-    pixels: Vec<Vec<Pixel>>,
+    pixels: Matrix<Pixel>,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -16,50 +19,40 @@ impl Image {
     }
 
     fn galaxy_x(&self, expansion: u32) -> Vec<u32> {
-        let mut result = Vec::new();
-        let mut x = 0;
-        for column in 0..self.width() {
-            let amount = self
-                .pixels
-                .iter()
-                .filter(|row| row[column] == Pixel::Galaxy)
-                .count();
-            if amount == 0 {
-                x += expansion;
-            } else {
-                for _ in 0..amount {
-                    result.push(x);
-                }
-                x += 1;
-            }
-        }
-        result
+        expanded_coordinates(self.pixels.columns(), expansion)
     }
 
     fn galaxy_y(&self, expansion: u32) -> Vec<u32> {
-        let mut result = Vec::new();
-        let mut y = 0;
-        for row in &self.pixels {
-            let amount = row.iter().filter(|&&pixel| pixel == Pixel::Galaxy).count();
-            if amount == 0 {
-                y += expansion;
-            } else {
-                for _ in 0..amount {
-                    result.push(y);
-                }
-                y += 1;
-            }
-        }
-        result
+        expanded_coordinates(self.pixels.rows(), expansion)
     }
+}
 
-    fn width(&self) -> usize {
-        self.pixels[0].len()
+/// The coordinate of every galaxy along one axis, given the lines crossing that axis in order.
+fn expanded_coordinates<'a, L>(lines: impl Iterator<Item = L>, expansion: u32) -> Vec<u32>
+where
+    L: IntoIterator<Item = &'a Pixel>,
+{
+    let mut result = Vec::new();
+    let mut coordinate = 0;
+    for line in lines {
+        let amount = line
+            .into_iter()
+            .filter(|&&pixel| pixel == Pixel::Galaxy)
+            .count();
+        if amount == 0 {
+            coordinate += expansion;
+        } else {
+            for _ in 0..amount {
+                result.push(coordinate);
+            }
+            coordinate += 1;
+        }
     }
+    result
 }
 
 fn distance_sums(positions: Vec<u32>) -> u64 {
-    let z = i64::try_from(positions.len() - 1).unwrap();
+    let z = i64::try_from(positions.len().saturating_sub(1)).unwrap();
     let sum: i64 = positions
         .into_iter()
         .enumerate()
@@ -68,24 +61,22 @@ fn distance_sums(positions: Vec<u32>) -> u64 {
     u64::try_from(sum).unwrap()
 }
 
-impl From<char> for Pixel {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Pixel {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            '.' => Self::EmptySpace,
-            '#' => Self::Galaxy,
-            _ => panic!("Invalid pixel: {c}"),
+            '.' => Ok(Self::EmptySpace),
+            '#' => Ok(Self::Galaxy),
+            _ => Err(ParseError::char("'.' or '#'", c)),
         }
     }
 }
 
 impl FromStr for Image {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let pixels = s
-            .lines()
-            .map(|line| line.chars().map(Pixel::from).collect())
-            .collect();
-        Ok(Self { pixels })
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Ok(Self { pixels: s.parse()? })
     }
 }
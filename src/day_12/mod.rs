@@ -1,41 +1,67 @@
 use std::str::FromStr;
 
+use crate::parse::ParseError;
+use crate::util::{self, ParIter as _};
+
 mod spring;
 
-fn solve(input: &str, unfold: bool) -> u64 {
+pub(crate) fn parse_input(input: &str) -> Result<Vec<spring::Row>, ParseError> {
     input
         .lines()
-        .map(|l| {
-            let Ok(mut row) = spring::Row::from_str(l);
-            if unfold {
-                row = row.unfold(5);
-            }
+        .map(spring::Row::from_str)
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(input))
+}
+
+fn total_arrangements(rows: &[spring::Row], unfold: bool) -> u64 {
+    util::par_iter(rows)
+        .map(|row| {
+            let mut row = if unfold {
+                row.clone().unfold(5)
+            } else {
+                row.clone()
+            };
             let mut solver = spring::Solver::new(&mut row);
             solver.arrangements()
         })
         .sum()
 }
 
+pub(crate) fn solve_1(rows: &[spring::Row]) -> u64 {
+    total_arrangements(rows, false)
+}
+
+pub(crate) fn solve_2(rows: &[spring::Row]) -> u64 {
+    total_arrangements(rows, true)
+}
+
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> u64 {
-    solve(input, false)
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    solve(input, true)
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -44,7 +70,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(7506, part_1(INPUT));
+        if let Some(input) = input::puzzle(12) {
+            assert_eq!(7506, part_1(&input));
+        }
     }
 
     #[test]
@@ -54,11 +82,24 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(548_241_300_348_335, part_2(INPUT));
+        if let Some(input) = input::puzzle(12) {
+            assert_eq!(548_241_300_348_335, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn impossible_rows() {
+        assert_eq!(0, part_1("#.# 1"));
+        assert_eq!(0, part_1("## 1"));
+        assert_eq!(1, part_1("#.# 1\n?.# 1,1"));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(12) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
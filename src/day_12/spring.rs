@@ -1,9 +1,10 @@
 use itertools::Itertools;
-use std::assert_matches::debug_assert_matches;
 use std::fmt::{Debug, Write};
 use std::str::FromStr;
 use std::{fmt, iter};
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug, Clone)]
 pub struct Row {
     springs: Vec<Spring>,
@@ -62,14 +63,14 @@ impl Row {
         Self { springs, groups }
     }
 
-    fn as_slice(&self) -> RowSlice<'_> {
+    /// The whole row, or nothing if its known springs already rule out every arrangement.
+    fn as_slice(&self) -> Option<RowSlice<'_>> {
         RowSlice {
             row: self,
             spring_offset: 0,
             group_offset: 0,
         }
         .simplify()
-        .unwrap()
     }
 }
 
@@ -83,7 +84,9 @@ impl<'a> Solver<'a> {
     }
 
     pub fn arrangements(&mut self) -> u64 {
-        self.arrangements_for_slice(self.row.as_slice())
+        self.row
+            .as_slice()
+            .map_or(0, |row| self.arrangements_for_slice(row))
     }
 
     fn arrangements_for_slice(&mut self, row: RowSlice) -> u64 {
@@ -104,8 +107,8 @@ impl<'a> Solver<'a> {
     }
 }
 
-impl<'a> RowSlice<'a> {
-    fn index(&self) -> usize {
+impl RowSlice<'_> {
+    const fn index(&self) -> usize {
         self.spring_offset * (self.row.groups.len() + 1) + self.group_offset
     }
 
@@ -123,7 +126,7 @@ impl<'a> RowSlice<'a> {
         if self.springs_is_empty() && !self.groups_is_empty() {
             return None;
         }
-        debug_assert_matches!(self.first_spring(), None | Some(Spring::Unknown));
+        debug_assert!(matches!(self.first_spring(), None | Some(Spring::Unknown)));
         Some(self)
     }
 
@@ -217,24 +220,29 @@ impl From<Spring> for char {
     }
 }
 
-impl From<char> for Spring {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Spring {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            '.' => Self::Operational,
-            '#' => Self::Damaged,
-            '?' => Self::Unknown,
-            _ => panic!("Invalid spring character: {c}"),
+            '.' => Ok(Self::Operational),
+            '#' => Ok(Self::Damaged),
+            '?' => Ok(Self::Unknown),
+            _ => Err(ParseError::char("a spring", c)),
         }
     }
 }
 
 impl FromStr for Row {
-    type Err = !;
-
-    fn from_str(s: &str) -> Result<Self, !> {
-        let (springs, groups) = s.split_once(' ').unwrap();
-        let springs = springs.chars().map(Spring::from).collect();
-        let groups = groups.split(',').map(|s| s.parse().unwrap()).collect();
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (springs, groups) = parse::split_once(s, " ")?;
+        let springs = parse::chars(springs, Spring::try_from).collect::<Result<_, _>>()?;
+        let groups = groups
+            .split(',')
+            .map(parse::number)
+            .collect::<Result<_, _>>()?;
         Ok(Self { springs, groups })
     }
 }
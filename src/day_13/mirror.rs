@@ -1,9 +1,12 @@
 use std::iter::zip;
 use std::str::FromStr;
 
+use crate::parse::ParseError;
+use crate::util::Matrix;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Valley {
-    pattern: Vec<Vec<Ground>>,
+    pattern: Matrix<Ground>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -13,44 +16,28 @@ enum Ground {
 }
 
 impl Valley {
-    #[allow(clippy::option_if_let_else)]
-    pub fn reflection_note(&self, smudged: bool) -> usize {
-        if let Some(columns) = self.vertical_reflection_line(smudged) {
-            columns
-        } else if let Some(rows) = self.horizontal_reflection_line(smudged) {
-            100 * rows
-        } else {
-            panic!("No reflection line found");
-        }
+    /// The columns left of the vertical reflection line, or 100 times the rows above the
+    /// horizontal one, if the valley has either.
+    pub fn reflection_note(&self, smudged: bool) -> Option<usize> {
+        self.vertical_reflection_line(smudged)
+            .or_else(|| Some(100 * self.horizontal_reflection_line(smudged)?))
     }
 
     fn vertical_reflection_line(&self, smudged: bool) -> Option<usize> {
-        (1..self.width()).find(|&columns| self.is_vertical_reflection_line(columns, smudged))
+        let transposed = Self {
+            pattern: self.pattern.transpose(),
+        };
+        transposed.horizontal_reflection_line(smudged)
     }
 
     fn horizontal_reflection_line(&self, smudged: bool) -> Option<usize> {
-        (1..self.height()).find(|&rows| self.is_horizontal_reflection_line(rows, smudged))
-    }
-
-    fn is_vertical_reflection_line(&self, columns: usize, smudged: bool) -> bool {
-        let mut has_inconsistency = false;
-        for row in &self.pattern {
-            for (a, b) in zip(row.iter().take(columns).rev(), row.iter().skip(columns)) {
-                if a != b {
-                    if !smudged || has_inconsistency {
-                        return false;
-                    }
-                    has_inconsistency = true;
-                }
-            }
-        }
-        has_inconsistency == smudged
+        (1..self.pattern.height()).find(|&rows| self.is_horizontal_reflection_line(rows, smudged))
     }
 
     fn is_horizontal_reflection_line(&self, rows: usize, smudged: bool) -> bool {
         let mut has_inconsistency = false;
-        let top = self.pattern.iter().take(rows).rev();
-        let bottom = self.pattern.iter().skip(rows);
+        let top = self.pattern.rows().take(rows).rev();
+        let bottom = self.pattern.rows().skip(rows);
         for (ra, rb) in zip(top, bottom) {
             for (a, b) in zip(ra, rb) {
                 if a != b {
@@ -63,34 +50,26 @@ impl Valley {
         }
         has_inconsistency == smudged
     }
-
-    fn width(&self) -> usize {
-        self.pattern[0].len()
-    }
-
-    fn height(&self) -> usize {
-        self.pattern.len()
-    }
 }
 
-impl From<char> for Ground {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Ground {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            '.' => Self::Ash,
-            '#' => Self::Rocks,
-            _ => panic!("Invalid ground type {c}"),
+            '.' => Ok(Self::Ash),
+            '#' => Ok(Self::Rocks),
+            _ => Err(ParseError::char("'.' or '#'", c)),
         }
     }
 }
 
 impl FromStr for Valley {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let pattern = s
-            .lines()
-            .map(|line| line.chars().map(Ground::from).collect())
-            .collect();
-        Ok(Self { pattern })
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            pattern: s.parse()?,
+        })
     }
 }
@@ -1,37 +1,65 @@
 use std::str::FromStr;
 
+use crate::parse::ParseError;
+use crate::util::{self, ParIter as _};
+
 mod mirror;
 
-fn sum_notes(input: &str, smudged: bool) -> usize {
+/// The valleys, each with its text.
+pub(crate) fn parse_input(input: &str) -> Result<Vec<(&str, mirror::Valley)>, ParseError> {
     input
         .split("\n\n")
-        .map(|p| {
-            let Ok(valley) = mirror::Valley::from_str(p);
-            valley.reflection_note(smudged)
+        .map(|text| Ok((text, mirror::Valley::from_str(text)?)))
+        .collect::<Result<_, _>>()
+        .map_err(|e: ParseError| e.locate(input))
+}
+
+/// The sum of the valleys' notes; errors point at the text of a valley without a reflection.
+fn sum_notes(valleys: &[(&str, mirror::Valley)], smudged: bool) -> Result<usize, ParseError> {
+    util::par_iter(valleys)
+        .map(|(text, valley)| {
+            valley
+                .reflection_note(smudged)
+                .ok_or_else(|| ParseError::new("a valley with a reflection line", text))
         })
         .sum()
 }
 
+pub(crate) fn solve_1(valleys: &[(&str, mirror::Valley)]) -> Result<usize, ParseError> {
+    sum_notes(valleys, false)
+}
+
+pub(crate) fn solve_2(valleys: &[(&str, mirror::Valley)]) -> Result<usize, ParseError> {
+    sum_notes(valleys, true)
+}
+
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    solve_1(&parse_input(input)?).map_err(|e| e.locate(input))
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    solve_2(&parse_input(input)?).map_err(|e| e.locate(input))
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    sum_notes(input, false)
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    sum_notes(input, true)
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -40,7 +68,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(29213, part_1(INPUT));
+        if let Some(input) = input::puzzle(13) {
+            assert_eq!(29213, part_1(&input));
+        }
     }
 
     #[test]
@@ -50,11 +80,26 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(37453, part_2(INPUT));
+        if let Some(input) = input::puzzle(13) {
+            assert_eq!(37453, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn no_reflection() {
+        let error = try_part_1("#.\n.#\n").unwrap_err();
+        assert_eq!("a valley with a reflection line", error.expected());
+        let error = try_part_2(&(EXAMPLE.to_string() + "\n\n##.\n..#\n")).unwrap_err();
+        assert_eq!("##.\n..#\n", error.found());
+        assert_eq!(17, error.location().unwrap().line);
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(13) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
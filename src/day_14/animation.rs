@@ -0,0 +1,186 @@
+use super::dish::Platform;
+use crate::util::{self, Cycle, Direction};
+use std::fmt::Write;
+
+/// Moves the cursor home and clears the terminal, so that printing the frames in turn
+/// plays them back in place.
+pub const CLEAR: &str = "\x1b[H\x1b[2J";
+
+const CELL: usize = 10;
+
+/// Every tilt of the spin cycles up to the first repeated state.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub frames: Vec<Frame>,
+    pub cycle: Cycle,
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// The spin cycle the frame belongs to, where 0 is the initial state.
+    pub spin: usize,
+    /// The tilt leading to the frame, or `None` for the initial state.
+    pub tilt: Option<Direction>,
+    pub platform: Platform,
+}
+
+impl Animation {
+    /// Spins the platform in the given order until it is back in a state it was in after an
+    /// earlier spin cycle, recording the platform after each tilt.
+    #[must_use]
+    pub fn record(platform: Platform, order: &[Direction]) -> Self {
+        let (cycle, _) = util::detect_cycle(platform.clone(), |platform| {
+            let mut platform = platform.clone();
+            platform.spin_cycle(order);
+            platform
+        });
+        let mut frames = vec![Frame {
+            spin: 0,
+            tilt: None,
+            platform: platform.clone(),
+        }];
+        let mut platform = platform;
+        for spin in 1..=cycle.start + cycle.length {
+            for &direction in order {
+                platform.tilt(direction);
+                frames.push(Frame {
+                    spin,
+                    tilt: Some(direction),
+                    platform: platform.clone(),
+                });
+            }
+        }
+        Self { frames, cycle }
+    }
+
+    /// What the frame shows, and whether it starts or closes the cycle.
+    #[must_use]
+    pub fn caption(&self, index: usize) -> String {
+        let frame = &self.frames[index];
+        let mut caption = frame.tilt.map_or_else(
+            || "Initial state".to_string(),
+            |direction| format!("Spin cycle {}, tilted {}", frame.spin, name(direction)),
+        );
+        let end_of_spin = index == self.frames.len() - 1
+            || self.frames[index + 1].spin != frame.spin
+            || frame.tilt.is_none();
+        if end_of_spin && frame.spin == self.cycle.start {
+            caption += " (cycle starts)";
+        } else if end_of_spin && frame.spin == self.cycle.start + self.cycle.length {
+            write!(
+                caption,
+                " (same as after spin cycle {}: the cycle is {} long)",
+                self.cycle.start, self.cycle.length
+            )
+            .unwrap();
+        } else if frame.spin > self.cycle.start {
+            caption += " (in cycle)";
+        }
+        caption
+    }
+
+    /// The frames as text, each preceded by [`CLEAR`] and followed by its caption.
+    #[must_use]
+    pub fn to_ascii(&self) -> String {
+        let mut ascii = String::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            writeln!(ascii, "{CLEAR}{}{}", frame.platform, self.caption(index)).unwrap();
+        }
+        ascii
+    }
+
+    /// An SVG document showing each frame for `delay_ms` milliseconds, on a loop.
+    #[must_use]
+    pub fn to_svg(&self, delay_ms: u64) -> String {
+        let rows: Vec<String> = self.frames[0]
+            .platform
+            .to_string()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let (height, width) = (rows.len(), rows[0].len());
+        let duration = delay_ms * self.frames.len() as u64;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+            width * CELL,
+            height * CELL + 2 * CELL
+        );
+        for (i, row) in rows.iter().enumerate() {
+            for (j, _) in row.match_indices('#') {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"dimgray\"/>",
+                    j * CELL,
+                    i * CELL
+                )
+                .unwrap();
+            }
+        }
+        let count = self.frames.len();
+        for (index, frame) in self.frames.iter().enumerate() {
+            let (values, key_times) = match index {
+                _ if count == 1 => ("visible".to_string(), "0".to_string()),
+                0 => (
+                    "visible;hidden".to_string(),
+                    format!("0;{}", fraction(1, count)),
+                ),
+                _ if index == count - 1 => (
+                    "hidden;visible".to_string(),
+                    format!("0;{}", fraction(index, count)),
+                ),
+                _ => (
+                    "hidden;visible;hidden".to_string(),
+                    format!(
+                        "0;{};{}",
+                        fraction(index, count),
+                        fraction(index + 1, count)
+                    ),
+                ),
+            };
+            writeln!(
+                svg,
+                "<g visibility=\"hidden\">\n<animate attributeName=\"visibility\" \
+                 values=\"{values}\" keyTimes=\"{key_times}\" dur=\"{duration}ms\" \
+                 calcMode=\"discrete\" repeatCount=\"indefinite\"/>"
+            )
+            .unwrap();
+            // Zero-length lines with round caps draw the rounded rocks as dots.
+            write!(
+                svg,
+                "<path stroke=\"peru\" stroke-linecap=\"round\" stroke-width=\"{}\" d=\"",
+                CELL * 4 / 5
+            )
+            .unwrap();
+            for (i, row) in frame.platform.to_string().lines().enumerate() {
+                for (j, _) in row.match_indices('O') {
+                    write!(svg, "M{} {}h0", j * CELL + CELL / 2, i * CELL + CELL / 2).unwrap();
+                }
+            }
+            svg += "\"/>\n";
+            writeln!(
+                svg,
+                "<text x=\"0\" y=\"{}\" font-family=\"monospace\" font-size=\"{CELL}\">{}</text>\n</g>",
+                height * CELL + CELL * 3 / 2,
+                self.caption(index)
+            )
+            .unwrap();
+        }
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+const fn name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "north",
+        Direction::Down => "south",
+        Direction::Left => "west",
+        Direction::Right => "east",
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fraction(numerator: usize, denominator: usize) -> String {
+    format!("{:.6}", numerator as f64 / denominator as f64)
+}
@@ -1,117 +1,245 @@
-use crate::util::Matrix;
+use crate::parse::ParseError;
+use crate::util::{Direction, Matrix, Position};
 use std::fmt;
 use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+use std::iter;
 use std::str::FromStr;
+use std::sync::Arc;
 
+/// The platform, holding the rounded rocks as row bitsets over a layout shared between copies.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Platform<const M: usize, const N: usize> {
-    rocks: Matrix<M, N, Option<Rock>>,
+pub struct Platform {
+    rounded: Lines,
+    layout: Arc<Layout>,
 }
 
+/// The cube-shaped rocks, and the runs of free cells between them along rows and columns.
+#[derive(Debug, PartialEq, Eq)]
+struct Layout {
+    height: usize,
+    width: usize,
+    cubes: Lines,
+    row_segments: Vec<Vec<Segment>>,
+    column_segments: Vec<Vec<Segment>>,
+}
+
+/// Lines of equal length packed into `u64` words, with bit `k` standing for the `k`th cell.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct Lines {
+    words: usize,
+    bits: Vec<u64>,
+}
+
+/// A run of cells between cube-shaped rocks or edges, that rounded rocks roll along.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Segment {
+    start: usize,
+    length: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 enum Rock {
     Rounded,
     CubeShaped,
 }
 
-//noinspection DuplicatedCode
-impl<const M: usize, const N: usize> Platform<M, N> {
-    pub fn spin_cycle(&mut self) {
-        self.tilt_north();
-        self.tilt_west();
-        self.tilt_south();
-        self.tilt_east();
-    }
-
-    pub fn tilt_north(&mut self) {
-        for i in 1..M {
-            for j in 0..N {
-                if self.rocks[i][j] == Some(Rock::Rounded) {
-                    if let Some(k) = (0..i)
-                        .rev()
-                        .take_while(|&k| self.rocks[k][j].is_none())
-                        .last()
-                    {
-                        self.rocks[i][j] = None;
-                        self.rocks[k][j] = Some(Rock::Rounded);
-                    }
-                }
-            }
+/// The puzzle's spin cycle: north, west, south and then east.
+pub const SPIN_CYCLE: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
+
+impl Platform {
+    /// Tilts the platform in each direction of the cycle in turn.
+    pub fn spin_cycle(&mut self, order: &[Direction]) {
+        for &direction in order {
+            self.tilt(direction);
+        }
+    }
+
+    /// Tilts the platform so that the rounded rocks roll towards the edge in the direction,
+    /// with up being north.
+    pub fn tilt(&mut self, direction: Direction) {
+        let to_end = matches!(direction, Direction::Down | Direction::Right);
+        let layout = &self.layout;
+        if direction.is_vertical() {
+            let mut columns = self.rounded.transpose(layout.width, layout.height);
+            columns.roll(&layout.column_segments, to_end);
+            self.rounded = columns.transpose(layout.height, layout.width);
+        } else {
+            self.rounded.roll(&layout.row_segments, to_end);
+        }
+    }
+
+    /// The load on the support beams along the edge in the direction: each rounded rock
+    /// weighs its distance from the opposite edge, counting its own cell.
+    #[must_use]
+    pub fn load(&self, direction: Direction) -> usize {
+        let (height, width) = (self.layout.height, self.layout.width);
+        let (lines, length) = if direction.is_vertical() {
+            (self.rounded.clone(), height)
+        } else {
+            (self.rounded.transpose(width, height), width)
+        };
+        (0..length)
+            .map(|k| {
+                let weight = match direction {
+                    Direction::Up | Direction::Left => length - k,
+                    Direction::Down | Direction::Right => k + 1,
+                };
+                let count: u32 = lines.line(k).iter().map(|word| word.count_ones()).sum();
+                weight * count as usize
+            })
+            .sum()
+    }
+
+    fn rock(&self, Position { i, j }: Position) -> Option<Rock> {
+        if self.rounded.get(i, j) {
+            Some(Rock::Rounded)
+        } else if self.layout.cubes.get(i, j) {
+            Some(Rock::CubeShaped)
+        } else {
+            None
         }
     }
+}
+
+impl Lines {
+    fn new(count: usize, length: usize) -> Self {
+        let words = length.div_ceil(64);
+        Self {
+            words,
+            bits: vec![0; count * words],
+        }
+    }
+
+    fn get(&self, line: usize, k: usize) -> bool {
+        self.bits[line * self.words + k / 64] >> (k % 64) & 1 == 1
+    }
 
-    pub fn tilt_west(&mut self) {
-        for i in 0..M {
-            for j in 1..N {
-                if self.rocks[i][j] == Some(Rock::Rounded) {
-                    if let Some(k) = (0..j)
-                        .rev()
-                        .take_while(|&k| self.rocks[i][k].is_none())
-                        .last()
-                    {
-                        self.rocks[i][j] = None;
-                        self.rocks[i][k] = Some(Rock::Rounded);
-                    }
+    fn set(&mut self, line: usize, k: usize) {
+        self.bits[line * self.words + k / 64] |= 1 << (k % 64);
+    }
+
+    /// The set bits of a line, in order.
+    fn ones(&self, line: usize) -> impl Iterator<Item = usize> + '_ {
+        self.line(line).iter().enumerate().flat_map(|(w, &word)| {
+            let mut bits = word;
+            iter::from_fn(move || {
+                (bits != 0).then(|| {
+                    let k = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    w * 64 + k
+                })
+            })
+        })
+    }
+
+    fn line(&self, line: usize) -> &[u64] {
+        &self.bits[line * self.words..(line + 1) * self.words]
+    }
+
+    /// The words of a line overlapping the segment, by index into `bits`, each with a mask
+    /// of its bits inside the segment.
+    fn masks(&self, line: usize, segment: Segment) -> impl Iterator<Item = (usize, u64)> {
+        let offset = line * self.words;
+        let end = segment.start + segment.length;
+        let mut k = segment.start;
+        iter::from_fn(move || {
+            (k < end).then(|| {
+                let bits = (end - k).min(64 - k % 64);
+                let mask = low_bits(bits) << (k % 64);
+                let index = offset + k / 64;
+                k += bits;
+                (index, mask)
+            })
+        })
+    }
+
+    fn count_ones(&self, line: usize, segment: Segment) -> usize {
+        self.masks(line, segment)
+            .map(|(index, mask)| (self.bits[index] & mask).count_ones() as usize)
+            .sum()
+    }
+
+    /// Moves the set bits in each line to the end of their segments, or to the start.
+    fn roll(&mut self, segments: &[Vec<Segment>], to_end: bool) {
+        for (line, segments) in segments.iter().enumerate() {
+            for &segment in segments {
+                let count = self.count_ones(line, segment);
+                for (index, mask) in self.masks(line, segment) {
+                    self.bits[index] &= !mask;
+                }
+                let start = if to_end {
+                    segment.start + segment.length - count
+                } else {
+                    segment.start
+                };
+                let rolled = Segment {
+                    start,
+                    length: count,
+                };
+                for (index, mask) in self.masks(line, rolled) {
+                    self.bits[index] |= mask;
                 }
             }
         }
     }
 
-    pub fn tilt_south(&mut self) {
-        for i in (0..M - 1).rev() {
-            for j in 0..N {
-                if self.rocks[i][j] == Some(Rock::Rounded) {
-                    if let Some(k) = (i + 1..M)
-                        .take_while(|&k| self.rocks[k][j].is_none())
-                        .last()
-                    {
-                        self.rocks[i][j] = None;
-                        self.rocks[k][j] = Some(Rock::Rounded);
-                    }
-                }
+    /// Turns `count` lines of `length` cells into `length` lines of `count` cells, visiting
+    /// only the set bits.
+    fn transpose(&self, length: usize, count: usize) -> Self {
+        let mut transposed = Self::new(length, count);
+        for line in 0..count {
+            for k in self.ones(line) {
+                transposed.set(k, line);
             }
         }
+        transposed
     }
 
-    pub fn tilt_east(&mut self) {
-        for i in 0..M {
-            for j in (0..N - 1).rev() {
-                if self.rocks[i][j] == Some(Rock::Rounded) {
-                    if let Some(k) = (j + 1..N)
-                        .take_while(|&k| self.rocks[i][k].is_none())
-                        .last()
-                    {
-                        self.rocks[i][j] = None;
-                        self.rocks[i][k] = Some(Rock::Rounded);
-                    }
-                }
+    /// The runs of unset bits along a line of `length` cells.
+    fn segments(&self, line: usize, length: usize) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for k in self.ones(line).chain(iter::once(length)) {
+            if k > start {
+                segments.push(Segment {
+                    start,
+                    length: k - start,
+                });
             }
+            start = k + 1;
         }
+        segments
     }
+}
 
-    pub fn north_support_beam_load(&self) -> usize {
-        self.rocks
-            .rows()
-            .rev()
-            .enumerate()
-            .map(|(index, row)| {
-                let rounded_rocks = row
-                    .iter()
-                    .filter(|&&rock| rock == Some(Rock::Rounded))
-                    .count();
-                (index + 1) * rounded_rocks
-            })
-            .sum()
+const fn low_bits(count: usize) -> u64 {
+    if count == 64 {
+        u64::MAX
+    } else {
+        (1 << count) - 1
+    }
+}
+
+/// Platforms sharing a layout differ only by their rounded rocks.
+impl Hash for Platform {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rounded.hash(state);
     }
 }
 
 impl Rock {
-    fn from_char(c: char) -> Option<Self> {
+    fn from_char(c: char) -> Result<Option<Self>, ParseError> {
         match c {
-            'O' => Some(Self::Rounded),
-            '#' => Some(Self::CubeShaped),
-            '.' => None,
-            _ => panic!("invalid rock char {c}"),
+            'O' => Ok(Some(Self::Rounded)),
+            '#' => Ok(Some(Self::CubeShaped)),
+            '.' => Ok(None),
+            _ => Err(ParseError::char("'O', '#' or '.'", c)),
         }
     }
 }
@@ -126,17 +254,44 @@ impl fmt::Display for Rock {
     }
 }
 
-impl<const M: usize, const N: usize> fmt::Display for Platform<M, N> {
+impl fmt::Display for Platform {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.rocks.fmt(f)
+        Matrix::from_fn(self.layout.height, self.layout.width, |position| {
+            self.rock(position)
+        })
+        .fmt(f)
     }
 }
 
-impl<const M: usize, const N: usize> FromStr for Platform<M, N> {
-    type Err = !;
+impl FromStr for Platform {
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let rocks = Matrix::from_str_map(s, Rock::from_char);
-        Ok(Self { rocks })
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let rocks = Matrix::from_str_map(s, Rock::from_char)?;
+        let (height, width) = (rocks.height(), rocks.width());
+        let bits = |rock| {
+            let mut lines = Lines::new(height, width);
+            for (Position { i, j }, _) in rocks.enumerate().filter(|&(_, &cell)| cell == Some(rock))
+            {
+                lines.set(i, j);
+            }
+            lines
+        };
+        let rounded = bits(Rock::Rounded);
+        let cubes = bits(Rock::CubeShaped);
+        let row_segments = (0..height).map(|i| cubes.segments(i, width)).collect();
+        let columns = cubes.transpose(width, height);
+        let column_segments = (0..width).map(|j| columns.segments(j, height)).collect();
+        let layout = Layout {
+            height,
+            width,
+            cubes,
+            row_segments,
+            column_segments,
+        };
+        Ok(Self {
+            rounded,
+            layout: Arc::new(layout),
+        })
     }
 }
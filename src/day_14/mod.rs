@@ -1,65 +1,216 @@
-use std::str::FromStr;
+use crate::parse::{self, ParseError};
+use crate::util::{self, Direction};
+use dish::Platform;
 
-mod dish;
+pub mod animation;
+pub mod dish;
 
 const CYCLES: usize = 1_000_000_000;
 
+pub(crate) fn parse_input(input: &str) -> Result<Platform, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(platform: &Platform) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt(Direction::Up);
+    platform.load(Direction::Up)
+}
+
+pub(crate) fn solve_2(platform: &Platform) -> usize {
+    load_after_cycles(platform.clone(), &dish::SPIN_CYCLE, CYCLES, Direction::Up)
+}
+
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
+/// The load on the beam in the given direction after spinning the platform `cycles` times.
 #[must_use]
-pub fn part_1<const M: usize, const N: usize>(input: &str) -> usize {
-    let Ok(mut platform) = dish::Platform::<N, N>::from_str(input);
-    platform.tilt_north();
-    platform.north_support_beam_load()
+pub fn load_after_cycles(
+    platform: Platform,
+    order: &[Direction],
+    cycles: usize,
+    beam: Direction,
+) -> usize {
+    let (cycle, states) = util::detect_cycle(platform, |platform| {
+        let mut platform = platform.clone();
+        platform.spin_cycle(order);
+        platform
+    });
+    states[cycle.index(cycles)].load(beam)
 }
 
 #[must_use]
-pub fn part_2<const M: usize, const N: usize>(input: &str) -> usize {
-    let Ok(mut platform) = dish::Platform::<N, N>::from_str(input);
-    let mut states = vec![platform.clone()];
-    for cycle in 1..=CYCLES {
-        platform.spin_cycle();
-        if let Some(position) = states.iter().position(|state| state == &platform) {
-            let repetition = &states[position..];
-            platform = repetition[(CYCLES - cycle) % repetition.len()].clone();
-            break;
-        }
-        states.push(platform.clone());
-    }
-    platform.north_support_beam_load()
+pub fn part_1(input: &str) -> usize {
+    try_part_1(input).unwrap()
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> usize {
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
+    use itertools::Itertools;
 
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
-        assert_eq!(136, part_1::<10, 10>(EXAMPLE));
+        assert_eq!(136, part_1(EXAMPLE));
     }
 
     #[test]
     fn answer_1() {
-        assert_eq!(110_565, part_1::<100, 100>(INPUT));
+        if let Some(input) = input::puzzle(14) {
+            assert_eq!(110_565, part_1(&input));
+        }
     }
 
     #[test]
     fn example_2() {
-        assert_eq!(64, part_2::<10, 10>(EXAMPLE));
+        assert_eq!(64, part_2(EXAMPLE));
+    }
+
+    #[test]
+    fn spin_cycle() {
+        let mut platform = parse_input(EXAMPLE).unwrap();
+        assert_eq!(EXAMPLE.trim_end(), platform.to_string().trim_end());
+        platform.spin_cycle(&dish::SPIN_CYCLE);
+        assert_eq!(
+            ".....#....\n\
+             ....#...O#\n\
+             ...OO##...\n\
+             .OO#......\n\
+             .....OOO#.\n\
+             .O#...O#.#\n\
+             ....O#....\n\
+             ......OOOO\n\
+             #...O###..\n\
+             #..OO#....\n",
+            platform.to_string()
+        );
+    }
+
+    #[test]
+    fn other_beams() {
+        let platform = parse_input(EXAMPLE).unwrap();
+        let order = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+        let mut spun = platform.clone();
+        spun.spin_cycle(&order);
+        spun.spin_cycle(&order);
+        assert_eq!(
+            spun.load(Direction::Right),
+            load_after_cycles(platform.clone(), &order, 2, Direction::Right)
+        );
+        let mut tilted = platform;
+        tilted.tilt(Direction::Right);
+        let rocks = EXAMPLE.matches('O').count();
+        assert_eq!(
+            rocks * 11,
+            tilted.load(Direction::Left) + tilted.load(Direction::Right)
+        );
+    }
+
+    #[test]
+    fn large_platform() {
+        let (height, width) = (150, 200);
+        let cell = |i: usize, j: usize| match (i * 7 + j * 13) % 17 {
+            0 => '#',
+            1..=5 => 'O',
+            _ => '.',
+        };
+        let rows: Vec<String> = (0..height)
+            .map(|i| (0..width).map(|j| cell(i, j)).collect())
+            .collect();
+        // Rolls each run between cube-shaped rocks by sorting its rounded rocks to the end.
+        let roll = |line: String| -> String {
+            line.split('#')
+                .map(|run| run.chars().sorted().collect::<String>())
+                .join("#")
+        };
+        let mut platform = parse_input(&rows.join("\n")).unwrap();
+        platform.tilt(Direction::Right);
+        let expected: Vec<String> = rows.iter().cloned().map(roll).collect();
+        assert_eq!(expected.join("\n") + "\n", platform.to_string());
+        platform.tilt(Direction::Down);
+        let load: usize = (0..width)
+            .map(|j| {
+                roll(
+                    expected
+                        .iter()
+                        .map(|row| char::from(row.as_bytes()[j]))
+                        .collect(),
+                )
+            })
+            .flat_map(|column| column.match_indices('O').map(|(i, _)| i + 1).collect_vec())
+            .sum();
+        assert_eq!(load, platform.load(Direction::Down));
+    }
+
+    #[test]
+    fn animation() {
+        let platform = parse_input(EXAMPLE).unwrap();
+        let animation = animation::Animation::record(platform, &dish::SPIN_CYCLE);
+        assert_eq!(
+            util::Cycle {
+                start: 3,
+                length: 7
+            },
+            animation.cycle
+        );
+        assert_eq!(1 + 4 * 10, animation.frames.len());
+        assert_eq!("Initial state", animation.caption(0));
+        assert_eq!("Spin cycle 1, tilted west", animation.caption(2));
+        assert_eq!(
+            "Spin cycle 3, tilted east (cycle starts)",
+            animation.caption(12)
+        );
+        assert_eq!(
+            "Spin cycle 4, tilted north (in cycle)",
+            animation.caption(13)
+        );
+        assert_eq!(
+            "Spin cycle 10, tilted east (same as after spin cycle 3: the cycle is 7 long)",
+            animation.caption(40)
+        );
+        assert_eq!(animation.frames[12].platform, animation.frames[40].platform);
+        assert_eq!(41, animation.to_ascii().matches(animation::CLEAR).count());
+        let svg = animation.to_svg(100);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(41, svg.matches("<animate ").count());
     }
 
     #[test]
     fn answer_2() {
-        assert_eq!(89845, part_2::<100, 100>(INPUT));
+        if let Some(input) = input::puzzle(14) {
+            assert_eq!(89845, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2::<100, 100>(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(14) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,8 +1,23 @@
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug)]
 pub struct InitializationSequence {
-    steps: Vec<String>,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Remove,
+    Insert { focal_length: usize },
 }
 
 #[derive(Debug)]
@@ -23,7 +38,7 @@ struct Lens {
 
 impl InitializationSequence {
     pub fn hash_sum(&self) -> usize {
-        self.steps.iter().map(|step| hash(step)).sum()
+        self.steps.iter().map(|step| hash(&step.text)).sum()
     }
 }
 
@@ -36,14 +51,13 @@ impl Facility {
 
     pub fn perform_initialization(&mut self, sequence: &InitializationSequence) {
         for step in &sequence.steps {
-            if let Some(label) = step.strip_suffix('-') {
-                self.box_mut(label).remove(label);
-            } else {
-                let (label, focal_length) = step.split_once('=').unwrap();
-                self.box_mut(label).insert(Lens {
-                    label: label.to_string(),
-                    focal_length: focal_length.parse().unwrap(),
-                });
+            let label = &step.label;
+            match step.operation {
+                Operation::Remove => self.box_mut(label).remove(label),
+                Operation::Insert { focal_length } => self.box_mut(label).insert(Lens {
+                    label: label.clone(),
+                    focal_length,
+                }),
             }
         }
     }
@@ -95,10 +109,33 @@ fn hash(string: &str) -> usize {
 }
 
 impl FromStr for InitializationSequence {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let steps = s.trim_end().split(',').map(str::to_string).collect();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let steps = s
+            .trim_end()
+            .split(',')
+            .map(Step::from_str)
+            .collect::<Result<_, _>>()?;
         Ok(Self { steps })
     }
 }
+
+impl FromStr for Step {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (label, operation) = if let Some(label) = s.strip_suffix('-') {
+            (label, Operation::Remove)
+        } else {
+            let (label, focal_length) = parse::split_once(s, "=")?;
+            let focal_length = parse::number(focal_length)?;
+            (label, Operation::Insert { focal_length })
+        };
+        Ok(Self {
+            text: s.to_string(),
+            label: label.to_string(),
+            operation,
+        })
+    }
+}
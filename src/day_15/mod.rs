@@ -1,31 +1,48 @@
-use std::str::FromStr;
+use crate::parse::{self, ParseError};
 
 mod lens;
 
+pub(crate) fn parse_input(input: &str) -> Result<lens::InitializationSequence, ParseError> {
+    parse::from_str(input)
+}
+
+pub(crate) fn solve_1(sequence: &lens::InitializationSequence) -> usize {
+    sequence.hash_sum()
+}
+
+pub(crate) fn solve_2(sequence: &lens::InitializationSequence) -> usize {
+    let mut facility = lens::Facility::new();
+    facility.perform_initialization(sequence);
+    facility.focussing_power()
+}
+
+pub fn try_part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> usize {
-    let Ok(sequence) = lens::InitializationSequence::from_str(input);
-    sequence.hash_sum()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> usize {
-    let Ok(sequence) = lens::InitializationSequence::from_str(input);
-    let mut facility = lens::Facility::new();
-    facility.perform_initialization(&sequence);
-    facility.focussing_power()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -34,7 +51,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(514_281, part_1(INPUT));
+        if let Some(input) = input::puzzle(15) {
+            assert_eq!(514_281, part_1(&input));
+        }
     }
 
     #[test]
@@ -44,11 +63,17 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(244_199, part_2(INPUT));
+        if let Some(input) = input::puzzle(15) {
+            assert_eq!(244_199, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(15) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,67 +1,245 @@
-use std::str::FromStr;
-
 use model::{Beam, Grid};
+use segments::Segments;
 
-use crate::util::{Direction, Position};
+use crate::parse::{self, ParseError};
+use crate::util::{self, Direction, ParIter as _, Position};
 
-mod model;
+pub mod model;
+pub mod segments;
 
-#[must_use]
-pub fn part_1<const N: usize>(input: &str) -> u32 {
-    let Ok(grid) = Grid::<N>::from_str(input);
-    Beam::new(&grid, Position::default(), Direction::Right).energy()
+pub(crate) fn parse_input(input: &str) -> Result<Grid, ParseError> {
+    parse::from_str(input)
 }
 
-#[must_use]
-pub fn part_2<const N: usize>(input: &str) -> u32 {
-    let Ok(grid) = Grid::<N>::from_str(input);
-    (0..N)
-        .flat_map(|i| {
-            [
-                (Position::new(0, i), Direction::Down),
-                (Position::new(N - 1, i), Direction::Up),
-                (Position::new(i, 0), Direction::Right),
-                (Position::new(i, N - 1), Direction::Left),
-            ]
-        })
-        .map(|(position, direction)| Beam::new(&grid, position, direction).energy())
+pub(crate) fn solve_1(grid: &Grid) -> u32 {
+    Beam::new(grid, Position::default(), Direction::Right).energy()
+}
+
+pub(crate) fn solve_2(grid: &Grid) -> u32 {
+    let Position { i: m, j: n } = grid.max_position();
+    let vertical = (0..=n).flat_map(|j| {
+        [
+            (Position::new(0, j), Direction::Down),
+            (Position::new(m, j), Direction::Up),
+        ]
+    });
+    let horizontal = (0..=m).flat_map(|i| {
+        [
+            (Position::new(i, 0), Direction::Right),
+            (Position::new(i, n), Direction::Left),
+        ]
+    });
+    let entries: Vec<_> = vertical.chain(horizontal).collect();
+    let segments = Segments::new(grid, entries.iter().copied());
+    util::par_iter(entries)
+        .map(|(position, direction)| segments.energy(position, direction))
         .max()
         .unwrap()
 }
 
+pub fn try_part_1(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_1(&parse_input(input)?))
+}
+
+pub fn try_part_2(input: &str) -> Result<u32, ParseError> {
+    Ok(solve_2(&parse_input(input)?))
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap()
+}
+
+#[must_use]
+pub fn part_2(input: &str) -> u32 {
+    try_part_2(input).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
+    use crate::util::DirectionSet;
+    use std::iter;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
-        assert_eq!(46, part_1::<10>(EXAMPLE));
+        assert_eq!(46, part_1(EXAMPLE));
+    }
+
+    #[test]
+    fn energized() {
+        let grid = parse_input(EXAMPLE).unwrap();
+        let energized = Beam::new(&grid, Position::default(), Direction::Right).trace();
+        assert_eq!(46, energized.count());
+        assert_eq!(
+            "######....\n\
+             .#...#....\n\
+             .#...#####\n\
+             .#...##...\n\
+             .#...##...\n\
+             .#...##...\n\
+             .#..####..\n\
+             ########..\n\
+             .#######..\n\
+             .#...#.#..\n",
+            energized.render()
+        );
+        assert_eq!(
+            ">|<<<\\....\n\
+             |v-.\\^....\n\
+             .v...|->>>\n\
+             .v...v^.|.\n\
+             .v...v^...\n\
+             .v...v^..\\\n\
+             .v../2\\\\..\n\
+             <->-/vv|..\n\
+             .|<<<2-|.\\\n\
+             .v//.|.v..\n",
+            energized.render_directions(&grid)
+        );
+        let svg = energized.to_svg(&grid);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(46 + 1, svg.matches("<rect").count());
     }
 
     #[test]
     fn answer_1() {
-        assert_eq!(7472, part_1::<110>(INPUT));
+        if let Some(input) = input::puzzle(16) {
+            assert_eq!(7472, part_1(&input));
+        }
     }
 
     #[test]
     fn example_2() {
-        assert_eq!(51, part_2::<10>(EXAMPLE));
+        assert_eq!(51, part_2(EXAMPLE));
+    }
+
+    #[test]
+    fn segments() {
+        let grid = parse_input(EXAMPLE).unwrap();
+        let entries = grid
+            .positions()
+            .flat_map(|position| Direction::ALL.map(|direction| (position, direction)));
+        let segments = Segments::new(&grid, entries);
+        for position in grid.positions() {
+            for direction in Direction::ALL {
+                assert_eq!(
+                    Beam::new(&grid, position, direction).energy(),
+                    segments.energy(position, direction),
+                    "{position:?} {direction:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_grid() {
+        let size = 600;
+        let mut seed = 2023_u64;
+        let mut random = || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            seed >> 33
+        };
+        let grid: String = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match random() % 40 {
+                        0 => '/',
+                        1 => '\\',
+                        2 => '-',
+                        3 => '|',
+                        _ => '.',
+                    })
+                    .chain(iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect();
+        let grid = parse_input(&grid).unwrap();
+        let entries: Vec<_> = (0..size)
+            .flat_map(|k| {
+                [
+                    (Position::new(0, k), Direction::Down),
+                    (Position::new(k, size - 1), Direction::Left),
+                ]
+            })
+            .collect();
+        let segments = Segments::new(&grid, entries.iter().copied());
+        assert!(segments.peak_sets() <= 2 * entries.len());
+        for &(position, direction) in entries.iter().step_by(97) {
+            assert_eq!(
+                Beam::new(&grid, position, direction).energy(),
+                segments.energy(position, direction)
+            );
+        }
+    }
+
+    #[test]
+    fn contraptions() {
+        let palette = model::Palette::extended();
+        let energy = |grid: &str, position, direction| {
+            let grid = palette.parse(grid).unwrap();
+            let segments = Segments::new(&grid, [(position, direction)]);
+            let energy = Beam::new(&grid, position, direction).energy();
+            assert_eq!(energy, segments.energy(position, direction));
+            energy
+        };
+        let start = Position::default();
+        assert_eq!(3, energy("..#..", start, Direction::Right));
+        assert_eq!(4, energy(".▶.◀.", start, Direction::Right));
+        assert_eq!(4, energy(".▶.◀.", Position::new(0, 4), Direction::Left));
+        assert_eq!(2, energy(".▶.◀.", Position::new(0, 2), Direction::Left));
+        let grid = palette.parse(".▶.◀.").unwrap();
+        let energized = Beam::new(&grid, start, Direction::Right).trace();
+        assert_eq!(">▶>◀.\n", energized.render_directions(&grid));
+        assert_eq!(3, energy(".R\n..", start, Direction::Right));
+        assert_eq!(2, energy(".L\n..", start, Direction::Right));
+        assert!("..#..".parse::<Grid>().is_err());
+
+        let mut palette = model::Palette::extended();
+        palette.insert(model::Tile::new('*', |direction| {
+            let mut directions: DirectionSet = direction.perpendicular().into_iter().collect();
+            directions.insert(direction);
+            directions
+        }));
+        let grid = palette.parse("...\n.*.\n.R.").unwrap();
+        let energized = Beam::new(&grid, Position::new(1, 0), Direction::Right).trace();
+        assert_eq!(".^.\n>*>\n<R.\n", energized.render_directions(&grid));
+        let segments = Segments::new(&grid, []);
+        for position in grid.positions() {
+            for direction in Direction::ALL {
+                assert_eq!(
+                    Beam::new(&grid, position, direction).energy(),
+                    segments.energy(position, direction)
+                );
+            }
+        }
+
+        palette.insert(model::Tile::mirror('a', true));
+        let grid = palette.parse("a*").unwrap();
+        let svg = Beam::new(&grid, start, Direction::Up).trace().to_svg(&grid);
+        assert_eq!(1, svg.matches("stroke=\"white\"").count());
+        assert_eq!(1, svg.matches("<text").count());
     }
 
     #[test]
     fn answer_2() {
-        assert_eq!(7716, part_2::<110>(INPUT));
+        if let Some(input) = input::puzzle(16) {
+            assert_eq!(7716, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_1::<110>(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(16) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,46 +1,68 @@
+use crate::parse::ParseError;
 use crate::util::{Direction, DirectionSet, Matrix, Position};
+use std::collections::BTreeMap;
+use std::fmt::Write;
 use std::ops::Deref;
 use std::str::FromStr;
 
 #[derive(Debug)]
-pub struct Grid<const N: usize>(Matrix<N, N, Tile>);
-
-#[derive(Debug, Copy, Clone, Default)]
-pub enum Tile {
-    #[default]
-    EmptySpace,
-    ForwardMirror,
-    BackwardMirror,
-    HorizontalSplitter,
-    VerticalSplitter,
+pub struct Grid(Matrix<Tile>);
+
+/// A tile, drawn as its symbol, with the directions a beam entering it in each direction
+/// leaves it in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tile {
+    symbol: char,
+    transfers: [DirectionSet; 4],
+}
+
+/// The tiles a grid can be made of, by symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette(BTreeMap<char, Tile>);
+
+/// The directions beams crossed each tile in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Energized {
+    visited: Matrix<DirectionSet>,
+    count: u32,
 }
 
 #[derive(Debug)]
-pub struct Beam<'a, const N: usize> {
-    grid: &'a Grid<N>,
+pub struct Beam<'a> {
+    grid: &'a Grid,
     energized: u32,
-    visited: Matrix<N, N, DirectionSet>,
-    sub_beams: Vec<(Position<N, N>, Direction)>,
+    visited: Matrix<DirectionSet>,
+    sub_beams: Vec<(Position, Direction)>,
 }
 
-impl<'a, const N: usize> Beam<'a, N> {
-    pub fn new(grid: &'a Grid<N>, position: Position<N, N>, direction: Direction) -> Self {
+impl<'a> Beam<'a> {
+    #[must_use]
+    pub fn new(grid: &'a Grid, position: Position, direction: Direction) -> Self {
         Self {
             grid,
             energized: 0,
-            visited: Matrix::default(),
+            visited: Matrix::new(grid.height(), grid.width()),
             sub_beams: vec![(position, direction)],
         }
     }
 
-    pub fn energy(mut self) -> u32 {
+    #[must_use]
+    pub fn energy(self) -> u32 {
+        self.trace().count
+    }
+
+    #[must_use]
+    pub fn trace(mut self) -> Energized {
         while let Some((position, direction)) = self.sub_beams.pop() {
             self.trace_section(position, direction);
         }
-        self.energized
+        Energized {
+            visited: self.visited,
+            count: self.energized,
+        }
     }
 
-    fn trace_section(&mut self, mut position: Position<N, N>, mut direction: Direction) {
+    fn trace_section(&mut self, mut position: Position, mut direction: Direction) {
         loop {
             let visited = &mut self.visited[position];
             if visited.is_empty() {
@@ -49,36 +71,17 @@ impl<'a, const N: usize> Beam<'a, N> {
             if !visited.insert(direction) {
                 return;
             }
-            match self.grid[position] {
-                Tile::ForwardMirror => {
-                    direction = match direction {
-                        Direction::Up => Direction::Right,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Down,
-                        Direction::Right => Direction::Up,
-                    }
-                }
-                Tile::BackwardMirror => {
-                    direction = match direction {
-                        Direction::Up => Direction::Left,
-                        Direction::Down => Direction::Right,
-                        Direction::Left => Direction::Up,
-                        Direction::Right => Direction::Down,
+            let directions = self.grid[position].transfer(direction);
+            if directions.len() != 1 {
+                for direction in directions.iter() {
+                    if let Some(next) = self.grid.neighbour(position, direction) {
+                        self.sub_beams.push((next, direction));
                     }
                 }
-                Tile::HorizontalSplitter if direction.is_vertical() => {
-                    self.sub_beams.push((position, Direction::Left));
-                    self.sub_beams.push((position, Direction::Right));
-                    return;
-                }
-                Tile::VerticalSplitter if direction.is_horizontal() => {
-                    self.sub_beams.push((position, Direction::Up));
-                    self.sub_beams.push((position, Direction::Down));
-                    return;
-                }
-                _ => {}
+                return;
             }
-            if let Some(p) = position.add(direction) {
+            direction = directions.iter().next().unwrap();
+            if let Some(p) = self.grid.neighbour(position, direction) {
                 position = p;
             } else {
                 return;
@@ -87,31 +90,299 @@ impl<'a, const N: usize> Beam<'a, N> {
     }
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
-        match c {
-            '.' => Self::EmptySpace,
-            '/' => Self::ForwardMirror,
-            '\\' => Self::BackwardMirror,
-            '-' => Self::HorizontalSplitter,
-            '|' => Self::VerticalSplitter,
-            _ => panic!("Invalid element: {c}"),
+const CELL: usize = 10;
+
+impl Energized {
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    #[must_use]
+    pub const fn visited(&self) -> &Matrix<DirectionSet> {
+        &self.visited
+    }
+
+    /// The puzzle's view, with energized tiles as `#` and the others as `.`.
+    #[must_use]
+    pub fn render(&self) -> String {
+        render(
+            &self
+                .visited
+                .map(|directions| if directions.is_empty() { '.' } else { '#' }),
+        )
+    }
+
+    /// The grid with each empty tile showing the direction of the beam crossing it, or the
+    /// number of directions if there are several.
+    #[must_use]
+    pub fn render_directions(&self, grid: &Grid) -> String {
+        render(&Matrix::from_fn(grid.height(), grid.width(), |position| {
+            let directions = self.visited[position];
+            let tile = grid[position];
+            match directions.len() {
+                1 if tile.is_transparent() => directions.iter().next().unwrap().arrow(),
+                n @ 2.. if tile.is_transparent() => {
+                    char::from_digit(u32::try_from(n).unwrap(), 10).unwrap()
+                }
+                _ => tile.symbol(),
+            }
+        }))
+    }
+
+    /// An SVG picture of the grid with energized tiles highlighted and the beams drawn
+    /// across them.
+    #[must_use]
+    pub fn to_svg(&self, grid: &Grid) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"black\"/>\n",
+            grid.width() * CELL,
+            grid.height() * CELL
+        );
+        for (Position { i, j }, directions) in self.visited.enumerate() {
+            let (x, y) = (j * CELL, i * CELL);
+            if !directions.is_empty() {
+                writeln!(
+                    svg,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"#553300\"/>"
+                )
+                .unwrap();
+            }
+            let (cx, cy) = (x + CELL / 2, y + CELL / 2);
+            if directions.iter().any(Direction::is_horizontal) {
+                line(&mut svg, (x, cy), (x + CELL, cy), "gold");
+            }
+            if directions.iter().any(Direction::is_vertical) {
+                line(&mut svg, (cx, y), (cx, y + CELL), "gold");
+            }
+            // Tiles acting like the puzzle's are drawn as lines whatever their symbol.
+            let tile = grid[Position::new(i, j)];
+            let stroke = if tile.acts_like(Tile::mirror('/', true)) {
+                Some(((x, y + CELL), (x + CELL, y)))
+            } else if tile.acts_like(Tile::mirror('\\', false)) {
+                Some(((x, y), (x + CELL, y + CELL)))
+            } else if tile.acts_like(Tile::splitter('-', true)) {
+                Some(((x, cy), (x + CELL, cy)))
+            } else if tile.acts_like(Tile::splitter('|', false)) {
+                Some(((cx, y), (cx, y + CELL)))
+            } else {
+                None
+            };
+            match stroke {
+                Some((from, to)) => line(&mut svg, from, to, "white"),
+                None if tile.is_transparent() => {}
+                None => writeln!(
+                    svg,
+                    "<text x=\"{cx}\" y=\"{}\" fill=\"white\" font-family=\"monospace\" \
+                     font-size=\"{CELL}\" text-anchor=\"middle\">{}</text>",
+                    y + CELL * 4 / 5,
+                    escape(tile.symbol())
+                )
+                .unwrap(),
+            }
+        }
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+fn render(matrix: &Matrix<char>) -> String {
+    matrix
+        .rows()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+fn line(svg: &mut String, (x1, y1): (usize, usize), (x2, y2): (usize, usize), colour: &str) {
+    writeln!(
+        svg,
+        "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{colour}\"/>"
+    )
+    .unwrap();
+}
+
+impl Tile {
+    /// A tile leading a beam entering in each direction out in the directions given by `rule`.
+    pub fn new(symbol: char, rule: impl Fn(Direction) -> DirectionSet) -> Self {
+        Self {
+            symbol,
+            transfers: Direction::ALL.map(rule),
         }
     }
+
+    /// Lets beams straight through.
+    #[must_use]
+    pub fn empty_space(symbol: char) -> Self {
+        Self::new(symbol, |direction| directions(&[direction]))
+    }
+
+    /// Reflects beams by 90 degrees: a forward mirror, like `/`, turns beams going up to the
+    /// right, and a backward one, like `\`, turns them to the left.
+    #[must_use]
+    pub fn mirror(symbol: char, forward: bool) -> Self {
+        Self::new(symbol, |direction| {
+            let reflected = if forward == direction.is_vertical() {
+                direction.turn_right()
+            } else {
+                direction.turn_left()
+            };
+            directions(&[reflected])
+        })
+    }
+
+    /// Splits beams crossing it into two going both ways along it, and lets beams along it
+    /// through.
+    #[must_use]
+    pub fn splitter(symbol: char, horizontal: bool) -> Self {
+        Self::new(symbol, |direction| {
+            if direction.is_horizontal() == horizontal {
+                directions(&[direction])
+            } else {
+                directions(&direction.perpendicular())
+            }
+        })
+    }
+
+    /// Absorbs every beam.
+    #[must_use]
+    pub fn blocker(symbol: char) -> Self {
+        Self::new(symbol, |_| DirectionSet::new())
+    }
+
+    /// Lets beams going the one way through, and absorbs all the others.
+    #[must_use]
+    pub fn prism(symbol: char, way: Direction) -> Self {
+        Self::new(symbol, |direction| {
+            if direction == way {
+                directions(&[direction])
+            } else {
+                DirectionSet::new()
+            }
+        })
+    }
+
+    /// Turns every beam by 90 degrees, clockwise or counterclockwise.
+    #[must_use]
+    pub fn rotator(symbol: char, clockwise: bool) -> Self {
+        Self::new(symbol, |direction| {
+            directions(&[if clockwise {
+                direction.turn_right()
+            } else {
+                direction.turn_left()
+            }])
+        })
+    }
+
+    /// The directions a beam entering the tile in the given direction leaves it in.
+    #[must_use]
+    pub const fn transfer(self, direction: Direction) -> DirectionSet {
+        self.transfers[direction.index()]
+    }
+
+    /// Whether beams go straight through the tile whichever way they enter it.
+    #[must_use]
+    pub fn is_transparent(self) -> bool {
+        Direction::ALL
+            .into_iter()
+            .all(|direction| self.transfer(direction) == directions(&[direction]))
+    }
+
+    /// Whether the tile leads beams the same way as the other, whatever their symbols.
+    #[must_use]
+    pub fn acts_like(self, other: Self) -> bool {
+        self.transfers == other.transfers
+    }
+
+    #[must_use]
+    pub const fn symbol(self) -> char {
+        self.symbol
+    }
+}
+
+fn directions(directions: &[Direction]) -> DirectionSet {
+    directions.iter().copied().collect()
+}
+
+fn escape(symbol: char) -> String {
+    match symbol {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        _ => symbol.to_string(),
+    }
+}
+
+impl Palette {
+    /// The puzzle's empty space, mirrors and splitters.
+    #[must_use]
+    pub fn puzzle() -> Self {
+        Self::from_iter([
+            Tile::empty_space('.'),
+            Tile::mirror('/', true),
+            Tile::mirror('\\', false),
+            Tile::splitter('-', true),
+            Tile::splitter('|', false),
+        ])
+    }
+
+    /// The puzzle's tiles along with blockers (`#`), one-way prisms (`▲`, `▼`, `◀` and `▶`,
+    /// unlike the arrows beams are drawn with) and rotators turning beams clockwise (`R`) or
+    /// counterclockwise (`L`).
+    #[must_use]
+    pub fn extended() -> Self {
+        let mut palette = Self::puzzle();
+        palette.insert(Tile::blocker('#'));
+        for (symbol, direction) in [
+            ('▲', Direction::Up),
+            ('▼', Direction::Down),
+            ('◀', Direction::Left),
+            ('▶', Direction::Right),
+        ] {
+            palette.insert(Tile::prism(symbol, direction));
+        }
+        palette.insert(Tile::rotator('R', true));
+        palette.insert(Tile::rotator('L', false));
+        palette
+    }
+
+    /// Adds a tile, replacing any with the same symbol.
+    pub fn insert(&mut self, tile: Tile) -> Option<Tile> {
+        self.0.insert(tile.symbol, tile)
+    }
+
+    #[must_use]
+    pub fn get(&self, symbol: char) -> Option<Tile> {
+        self.0.get(&symbol).copied()
+    }
+
+    pub fn parse(&self, s: &str) -> Result<Grid, ParseError> {
+        let expected = format!("one of {:?}", self.0.keys().collect::<String>());
+        let tiles = Matrix::from_str_map(s, |c| {
+            self.get(c).ok_or_else(|| ParseError::char(&expected, c))
+        })?;
+        Ok(Grid(tiles))
+    }
+}
+
+impl FromIterator<Tile> for Palette {
+    fn from_iter<I: IntoIterator<Item = Tile>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|tile| (tile.symbol, tile)).collect())
+    }
 }
 
-impl<const N: usize> Deref for Grid<N> {
-    type Target = Matrix<N, N, Tile>;
+impl Deref for Grid {
+    type Target = Matrix<Tile>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<const N: usize> FromStr for Grid<N> {
-    type Err = !;
+impl FromStr for Grid {
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        Ok(Self(Matrix::from_str(s)?))
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Palette::puzzle().parse(s)
     }
 }
@@ -0,0 +1,221 @@
+use std::mem;
+
+use super::model::{Beam, Grid};
+use crate::util::{self, Direction, DirectionMap, Matrix, Position};
+
+/// The beams through a grid as a graph of straight segments between mirrors and splitters.
+///
+/// Each node is a mirror or splitter entered in some direction, leading along the segments it
+/// sends beams down to the next ones. Nodes that lead to each other are condensed into
+/// components, and those reached from the entries given up front hold every tile their beams
+/// energize.
+#[derive(Debug)]
+pub struct Segments<'a> {
+    grid: &'a Grid,
+    nodes: Matrix<DirectionMap<usize>>,
+    components: Vec<usize>,
+    energized: Vec<Option<TileSet>>,
+    peak_sets: usize,
+}
+
+/// A set of tiles, one bit each in row-major order.
+#[derive(Debug, Clone)]
+struct TileSet(Vec<u64>);
+
+impl<'a> Segments<'a> {
+    /// Prepares for beams entering at the given positions and directions. Each component's
+    /// tiles are only kept until every component leading to it has taken them in, unless an
+    /// entry reaches it directly.
+    #[must_use]
+    pub fn new(grid: &'a Grid, entries: impl IntoIterator<Item = (Position, Direction)>) -> Self {
+        let mut nodes: Matrix<DirectionMap<usize>> = Matrix::new(grid.height(), grid.width());
+        let mut starts = Vec::new();
+        for (position, &tile) in grid.enumerate() {
+            if !tile.is_transparent() {
+                for direction in Direction::ALL {
+                    nodes[position].insert(direction, starts.len());
+                    starts.push((position, direction));
+                }
+            }
+        }
+        let (edges, tiles): (Vec<Vec<usize>>, Vec<Vec<Position>>) = starts
+            .iter()
+            .map(|&(position, direction)| {
+                let mut edges = Vec::new();
+                let mut tiles = vec![position];
+                for direction in grid[position].transfer(direction).iter() {
+                    let (passed, next) = walk(grid, position, direction);
+                    tiles.extend(passed);
+                    edges.extend(next.map(|(position, direction)| nodes[position][direction]));
+                }
+                (edges, tiles)
+            })
+            .unzip();
+
+        // Components come with those they lead to first, so their tiles are known in time.
+        let members = util::strongly_connected_components(&edges);
+        let mut components = vec![0; starts.len()];
+        for (index, members) in members.iter().enumerate() {
+            for &node in members {
+                components[node] = index;
+            }
+        }
+        let children: Vec<Vec<usize>> = members
+            .iter()
+            .enumerate()
+            .map(|(index, members)| {
+                let mut children: Vec<usize> = members
+                    .iter()
+                    .flat_map(|&node| &edges[node])
+                    .map(|&next| components[next])
+                    .filter(|&child| child != index)
+                    .collect();
+                children.sort_unstable();
+                children.dedup();
+                children
+            })
+            .collect();
+
+        // Only components reachable from the entries are needed, each until its last parent.
+        let mut kept = vec![false; members.len()];
+        let mut needed = vec![false; members.len()];
+        let mut todo = Vec::new();
+        for (position, direction) in entries {
+            if let Some(node) = first_node(grid, position, direction) {
+                let component = components[nodes[node.0][node.1]];
+                kept[component] = true;
+                todo.push(component);
+            }
+        }
+        let mut parents = vec![0_usize; members.len()];
+        while let Some(component) = todo.pop() {
+            if !mem::replace(&mut needed[component], true) {
+                for &child in &children[component] {
+                    parents[child] += 1;
+                    todo.push(child);
+                }
+            }
+        }
+
+        let size = grid.height() * grid.width();
+        let mut energized: Vec<Option<TileSet>> = vec![None; members.len()];
+        let (mut live, mut peak_sets) = (0, 0);
+        for (index, members) in members.iter().enumerate() {
+            if !needed[index] {
+                continue;
+            }
+            let mut set = TileSet::new(size);
+            for &node in members {
+                for &position in &tiles[node] {
+                    set.insert(grid.width(), position);
+                }
+            }
+            for &child in &children[index] {
+                set.union_with(energized[child].as_ref().unwrap());
+                parents[child] -= 1;
+                if parents[child] == 0 && !kept[child] {
+                    energized[child] = None;
+                    live -= 1;
+                }
+            }
+            energized[index] = Some(set);
+            live += 1;
+            peak_sets = peak_sets.max(live);
+        }
+        Self {
+            grid,
+            nodes,
+            components,
+            energized,
+            peak_sets,
+        }
+    }
+
+    /// The number of tiles energized by a beam entering at the position in the direction,
+    /// tracing it afresh if it was not among the entries the segments were prepared for.
+    #[must_use]
+    pub fn energy(&self, position: Position, direction: Direction) -> u32 {
+        let (mut passed, node) = if self.grid[position].is_transparent() {
+            walk(self.grid, position, direction)
+        } else {
+            (Vec::new(), Some((position, direction)))
+        };
+        passed.push(position);
+        let Some((node, _)) = node else {
+            return u32::try_from(passed.len()).unwrap();
+        };
+        let Some(energized) = &self.energized[self.components[self.nodes[node][direction]]] else {
+            return Beam::new(self.grid, position, direction).energy();
+        };
+        let width = self.grid.width();
+        let outside = passed
+            .iter()
+            .filter(|&&position| !energized.contains(width, position))
+            .count();
+        energized.len() + u32::try_from(outside).unwrap()
+    }
+
+    /// The most tile sets held at once while preparing, which bounds the memory used.
+    #[must_use]
+    pub const fn peak_sets(&self) -> usize {
+        self.peak_sets
+    }
+}
+
+/// The mirror or splitter a beam entering at the position in the direction reaches first,
+/// with its direction then, which is the entry itself if it starts on one.
+fn first_node(
+    grid: &Grid,
+    position: Position,
+    direction: Direction,
+) -> Option<(Position, Direction)> {
+    if grid[position].is_transparent() {
+        walk(grid, position, direction).1
+    } else {
+        Some((position, direction))
+    }
+}
+
+/// Follows a beam leaving the position in the direction across empty space, returning the
+/// tiles it passes and the mirror or splitter it reaches, if any, with its direction then.
+fn walk(
+    grid: &Grid,
+    mut position: Position,
+    direction: Direction,
+) -> (Vec<Position>, Option<(Position, Direction)>) {
+    let mut passed = Vec::new();
+    while let Some(next) = grid.neighbour(position, direction) {
+        position = next;
+        if !grid[position].is_transparent() {
+            return (passed, Some((position, direction)));
+        }
+        passed.push(position);
+    }
+    (passed, None)
+}
+
+impl TileSet {
+    fn new(size: usize) -> Self {
+        Self(vec![0; size.div_ceil(64)])
+    }
+
+    fn insert(&mut self, width: usize, Position { i, j }: Position) {
+        let index = i * width + j;
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    fn contains(&self, width: usize, Position { i, j }: Position) -> bool {
+        let index = i * width + j;
+        self.0[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word |= other;
+        }
+    }
+
+    fn len(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+}
@@ -1,85 +1,214 @@
-use crate::util::{Direction, Matrix, OrientationSet, Position};
+use crate::parse::ParseError;
+use crate::util::{self, Direction, DirectionSet, Matrix, Orientation, Position};
 use itertools::unfold;
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::iter;
 use std::str::FromStr;
 
 #[derive(Clone, Debug)]
-pub struct Map<const M: usize, const N: usize> {
-    heat_loss: Matrix<M, N, u32>,
+pub struct Map {
+    heat_loss: Matrix<u32>,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Crucible<const M: usize, const N: usize> {
-    heat_loss: u32,
-    position: Position<M, N>,
-    direction: Direction,
+/// A crucible stopped after a straight move, or at the start if it has not moved yet.
+///
+/// When the query's turns only depend on the orientation of the last move, both directions
+/// along it share a state, keyed by [`Query::heading`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Crucible {
+    position: Position,
+    direction: Option<Direction>,
 }
 
-impl<const M: usize, const N: usize> Map<M, N> {
-    pub fn least_heat_loss(&self, min_move: usize, max_move: usize) -> u32 {
-        let mut paths = BinaryHeap::new();
-        paths.push(Crucible {
-            heat_loss: 0,
-            position: Position::default(),
-            direction: Direction::Down,
-        });
-        paths.push(Crucible {
-            heat_loss: 0,
-            position: Position::default(),
-            direction: Direction::Right,
-        });
-        let mut visited: Matrix<M, N, OrientationSet> = Matrix::default();
-        while let Some(crucible) = paths.pop() {
-            if crucible.position == Position::MAX {
-                return crucible.heat_loss;
-            }
-            if !visited[crucible.position].insert(crucible.direction.orientation()) {
-                continue;
-            }
-            for direction in crucible.direction.perpendicular() {
-                unfold(crucible, |c| {
-                    let res = c.goto(direction, &self.heat_loss);
-                    if let Some(res) = res {
-                        *c = res;
-                    }
-                    res
-                })
-                .take(max_move)
-                .skip(min_move - 1)
-                .for_each(|crucible| paths.push(crucible));
-            }
+/// Where a crucible travels and the rules it moves by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Query {
+    pub start: Position,
+    pub goal: Position,
+    pub min_move: usize,
+    pub max_move: usize,
+    /// The directions the first move may take.
+    pub initial_directions: DirectionSet,
+    pub turns: Turns,
+}
+
+/// Which directions a crucible may take after a move besides turning left or right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Turns {
+    /// Going back the way it came.
+    pub reverse: bool,
+    /// Carrying on in the same direction, allowing straight runs longer than `max_move`.
+    pub straight: bool,
+}
+
+/// A path through the city, as a series of straight moves from its start.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub start: Position,
+    pub moves: Vec<Move>,
+    pub heat_loss: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub direction: Direction,
+    pub distance: usize,
+}
+
+impl Map {
+    #[must_use]
+    pub fn least_heat_loss(&self, min_move: usize, max_move: usize) -> Option<u32> {
+        Some(self.optimal_route(min_move, max_move)?.heat_loss)
+    }
+
+    #[must_use]
+    pub fn optimal_route(&self, min_move: usize, max_move: usize) -> Option<Route> {
+        self.route(&self.query(min_move, max_move))
+    }
+
+    /// The puzzle's query: from the top left to the bottom right, starting in any direction
+    /// and turning left or right after every move.
+    #[must_use]
+    pub const fn query(&self, min_move: usize, max_move: usize) -> Query {
+        Query {
+            start: Position::new(0, 0),
+            goal: self.heat_loss.max_position(),
+            min_move,
+            max_move,
+            initial_directions: DirectionSet::ALL,
+            turns: Turns {
+                reverse: false,
+                straight: false,
+            },
         }
-        panic!("No path found");
     }
-}
 
-impl<const M: usize, const N: usize> Crucible<M, N> {
-    fn goto(mut self, direction: Direction, heat_losses: &Matrix<M, N, u32>) -> Option<Self> {
-        self.direction = direction;
-        self.position = self.position.add(direction)?;
-        self.heat_loss += heat_losses[self.position];
-        Some(self)
+    /// The route losing the least heat for the query, if the goal can be reached.
+    #[must_use]
+    pub fn route(&self, query: &Query) -> Option<Route> {
+        if !self.heat_loss.contains(query.start) || !self.heat_loss.contains(query.goal) {
+            return None;
+        }
+        let start = Crucible {
+            position: query.start,
+            direction: None,
+        };
+        let (heat_loss, path) = util::dijkstra(
+            [start],
+            |crucible| self.moves(*crucible, query),
+            |crucible| crucible.position == query.goal,
+        )?;
+        let moves = path
+            .windows(2)
+            .map(|w| Move::between(w[0].position, w[1].position))
+            .collect();
+        Some(Route {
+            start: query.start,
+            moves,
+            heat_loss,
+        })
+    }
+
+    /// The map with every block the route enters replaced by the direction it entered in.
+    #[must_use]
+    pub fn render(&self, route: &Route) -> String {
+        let mut blocks = self
+            .heat_loss
+            .map(|&heat_loss| char::from_digit(heat_loss, 10).unwrap());
+        let mut position = route.start;
+        for step in route
+            .moves
+            .iter()
+            .flat_map(|step| iter::repeat_n(step.direction, step.distance))
+        {
+            position = position.step(step).unwrap();
+            blocks[position] = step.arrow();
+        }
+        blocks
+            .rows()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    /// The crucibles reachable by turning as the query allows and then moving between
+    /// `min_move` and `max_move` blocks, with the heat lost on the way.
+    fn moves<'a>(
+        &'a self,
+        crucible: Crucible,
+        query: &'a Query,
+    ) -> impl Iterator<Item = (Crucible, u32)> + 'a {
+        let directions = crucible
+            .direction
+            .map_or(query.initial_directions, |direction| {
+                let mut directions: DirectionSet = direction.perpendicular().into_iter().collect();
+                if query.turns.reverse {
+                    directions.insert(direction.opposite());
+                }
+                if query.turns.straight {
+                    directions.insert(direction);
+                }
+                directions
+            });
+        directions.iter().flat_map(move |direction| {
+            let mut heat_loss = 0;
+            unfold(crucible.position, move |position| {
+                *position = self.heat_loss.neighbour(*position, direction)?;
+                heat_loss += self.heat_loss[*position];
+                Some((
+                    Crucible {
+                        position: *position,
+                        direction: Some(query.heading(direction)),
+                    },
+                    heat_loss,
+                ))
+            })
+            .take(query.max_move)
+            .skip(query.min_move.saturating_sub(1))
+        })
     }
 }
 
-impl<const M: usize, const N: usize> Ord for Crucible<M, N> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.heat_loss.cmp(&self.heat_loss)
+impl Query {
+    /// The direction a crucible is keyed by after moving in `direction`. Unless exactly one
+    /// of reversing or going straight is allowed, the next directions only depend on the
+    /// orientation, so both directions along it map to one.
+    #[must_use]
+    pub const fn heading(&self, direction: Direction) -> Direction {
+        if self.turns.reverse != self.turns.straight {
+            return direction;
+        }
+        match direction.orientation() {
+            Orientation::Vertical => Direction::Down,
+            Orientation::Horizontal => Direction::Right,
+        }
     }
 }
 
-impl<const M: usize, const N: usize> PartialOrd for Crucible<M, N> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Move {
+    /// The straight move from one position to another in the same row or column.
+    const fn between(from: Position, to: Position) -> Self {
+        let direction = if to.i < from.i {
+            Direction::Up
+        } else if to.i > from.i {
+            Direction::Down
+        } else if to.j < from.j {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+        Self {
+            direction,
+            distance: from.i.abs_diff(to.i) + from.j.abs_diff(to.j),
+        }
     }
 }
 
-impl<const M: usize, const N: usize> FromStr for Map<M, N> {
-    type Err = !;
+impl FromStr for Map {
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let heat_loss = Matrix::from_str_map(s, |c| c.to_digit(10).unwrap());
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let heat_loss = Matrix::from_str_map(s, |c| {
+            c.to_digit(10).ok_or_else(|| ParseError::char("a digit", c))
+        })?;
         Ok(Self { heat_loss })
     }
 }
@@ -1,60 +1,138 @@
-use std::str::FromStr;
-mod city;
+use crate::parse::{self, ParseError};
+use crate::solver::SolveError;
 
-fn solve<const M: usize, const N: usize>(input: &str, min_move: usize, max_move: usize) -> u32 {
-    let Ok(map) = city::Map::<M, N>::from_str(input);
+pub mod city;
+
+pub(crate) fn parse_input(input: &str) -> Result<city::Map, ParseError> {
+    parse::from_str(input)
+}
+
+fn least_heat_loss(map: &city::Map, min_move: usize, max_move: usize) -> Result<u32, SolveError> {
     map.least_heat_loss(min_move, max_move)
+        .ok_or(SolveError::NoAnswer("there is no route to the goal"))
+}
+
+pub(crate) fn solve_1(map: &city::Map) -> Result<u32, SolveError> {
+    least_heat_loss(map, 1, 3)
+}
+
+pub(crate) fn solve_2(map: &city::Map) -> Result<u32, SolveError> {
+    least_heat_loss(map, 4, 10)
+}
+
+pub fn try_part_1(input: &str) -> Result<u32, SolveError> {
+    solve_1(&parse_input(input)?)
+}
+
+pub fn try_part_2(input: &str) -> Result<u32, SolveError> {
+    solve_2(&parse_input(input)?)
 }
 
 #[must_use]
-pub fn part_1<const M: usize, const N: usize>(input: &str) -> u32 {
-    solve::<M, N>(input, 1, 3)
+pub fn part_1(input: &str) -> u32 {
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
-pub fn part_2<const M: usize, const N: usize>(input: &str) -> u32 {
-    solve::<M, N>(input, 4, 10)
+pub fn part_2(input: &str) -> u32 {
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
+    use crate::util::{Direction, Position};
+    use std::iter;
 
     const EXAMPLE: &str = include_str!("example.txt");
     const MINI_EXAMPLE: &str = include_str!("mini_example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
-        assert_eq!(102, part_1::<13, 13>(EXAMPLE));
+        assert_eq!(102, part_1(EXAMPLE));
     }
 
     #[test]
     fn answer_1() {
-        assert_eq!(771, part_1::<141, 141>(INPUT));
+        if let Some(input) = input::puzzle(17) {
+            assert_eq!(771, part_1(&input));
+        }
     }
 
     #[test]
     fn example_2() {
-        assert_eq!(94, part_2::<13, 13>(EXAMPLE));
+        assert_eq!(94, part_2(EXAMPLE));
     }
 
     #[test]
     fn mini_example() {
-        assert_eq!(71, part_2::<5, 12>(MINI_EXAMPLE));
+        assert_eq!(71, part_2(MINI_EXAMPLE));
+    }
+
+    #[test]
+    fn route() {
+        let map = parse_input(MINI_EXAMPLE).unwrap();
+        let route = map.optimal_route(4, 10).unwrap();
+        assert_eq!(71, route.heat_loss);
+        assert_eq!(
+            "1>>>>>>>1111\n\
+             9999999v9991\n\
+             9999999v9991\n\
+             9999999v9991\n\
+             9999999v>>>>\n",
+            map.render(&route)
+        );
+        let route = parse_input(EXAMPLE).unwrap().optimal_route(1, 3).unwrap();
+        assert_eq!(102, route.heat_loss);
+        assert!(route
+            .moves
+            .iter()
+            .all(|step| (1..=3).contains(&step.distance)));
+    }
+
+    #[test]
+    fn query() {
+        let map = parse_input(MINI_EXAMPLE).unwrap();
+        let mut query = map.query(4, 10);
+        query.goal = Position::new(0, 11);
+        let turning = map.route(&query).unwrap();
+        assert!(turning.heat_loss > 11);
+        query.turns.straight = true;
+        assert_eq!(Some(11), map.route(&query).map(|route| route.heat_loss));
+        query.start = Position::new(4, 11);
+        query.goal = Position::new(4, 0);
+        query.initial_directions = iter::once(Direction::Left).collect();
+        assert_eq!(Some(99), map.route(&query).map(|route| route.heat_loss));
+        query.goal = Position::new(5, 0);
+        assert_eq!(None, map.route(&query));
+    }
+
+    #[test]
+    fn no_route() {
+        assert_eq!(
+            Err(SolveError::NoAnswer("there is no route to the goal")),
+            try_part_1("2413432")
+        );
+        assert_eq!(Ok(17), try_part_2("2413432"));
     }
 
     #[test]
     fn answer_2() {
-        assert_eq!(930, part_2::<141, 141>(INPUT));
+        if let Some(input) = input::puzzle(17) {
+            assert_eq!(930, part_2(&input));
+        }
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2::<141, 141>(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(17) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -1,6 +1,8 @@
-use crate::util::{Direction, Vector2D};
+use crate::util::{Direction, Polygon, Vector2D};
 use std::str::FromStr;
 
+use crate::parse::{self, ParseError};
+
 pub struct DigPlan {
     pub steps: Vec<DigStep>,
 }
@@ -11,52 +13,35 @@ pub struct DigStep {
 }
 
 impl DigPlan {
+    /// The dug cells: the lattice points inside the trench polygon or on it.
     pub fn volume(&self) -> u64 {
-        let vertices = self.vertices();
-        let area = vertices
-            .windows(2)
-            .map(|w| w[0].cross_product(w[1]))
-            .sum::<i64>()
-            .unsigned_abs()
-            / 2;
-        let boundary = self.steps.iter().map(|step| step.distance).sum::<u64>();
-        area + boundary / 2 + 1
-    }
-
-    fn vertices(&self) -> Vec<Vector2D> {
-        let mut vertices = Vec::with_capacity(self.steps.len() + 1);
-        let mut position = Vector2D::ZERO;
-        vertices.push(position);
-        for step in &self.steps {
-            position += Vector2D::from(step.direction) * i64::try_from(step.distance).unwrap();
-            vertices.push(position);
-        }
-        vertices
+        Polygon::from_moves(
+            self.steps
+                .iter()
+                .map(|step| Vector2D::from(step.direction) * i64::try_from(step.distance).unwrap()),
+        )
+        .enclosed_points()
     }
 }
 
 impl FromStr for DigPlan {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let steps = s
-            .lines()
-            .map(|line| line.parse().unwrap())
-            .collect::<Vec<_>>();
+        let steps = s.lines().map(DigStep::from_str).collect::<Result<_, _>>()?;
         Ok(Self { steps })
     }
 }
 
 impl FromStr for DigStep {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(' ');
-        let direction = split.next().unwrap().parse().unwrap();
-        let distance = split.next().unwrap().parse().unwrap();
+        let (direction, rest) = parse::split_once(s, " ")?;
+        let (distance, _) = parse::split_once(rest, " ")?;
         Ok(Self {
-            direction,
-            distance,
+            direction: direction.parse()?,
+            distance: parse::number(distance)?,
         })
     }
 }
@@ -1,54 +1,67 @@
+use crate::parse::{self, ParseError};
 use crate::util::Direction;
-use std::str::FromStr;
 
 mod lagoon;
 
+pub(crate) fn parse_input(input: &str) -> Result<lagoon::DigPlan, ParseError> {
+    parse::from_str(input)
+}
+
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    let dig_plan = parse_input(input)?;
+    Ok(dig_plan.volume())
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    let steps = input
+        .lines()
+        .map(parse_with_color_swap)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(input))?;
+    let dig_plan = lagoon::DigPlan { steps };
+    Ok(dig_plan.volume())
+}
+
 #[must_use]
 pub fn part_1(input: &str) -> u64 {
-    let Ok(dig_plan) = lagoon::DigPlan::from_str(input);
-    dig_plan.volume()
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    let steps = input.lines().map(parse_with_color_swap).collect();
-    let dig_plan = lagoon::DigPlan { steps };
-    dig_plan.volume()
+    try_part_2(input).unwrap()
 }
 
-fn parse_with_color_swap(line: &str) -> lagoon::DigStep {
-    let s = line
-        .split(' ')
-        .nth(2)
-        .unwrap()
-        .strip_prefix("(#")
-        .unwrap()
-        .strip_suffix(')')
-        .unwrap();
-    let distance = u64::from_str_radix(&s[0..(s.len() - 1)], 16).unwrap();
-    let direction = match s.chars().last().unwrap() {
-        '0' => Direction::Right,
-        '1' => Direction::Down,
-        '2' => Direction::Left,
-        '3' => Direction::Up,
-        _ => panic!("Invalid direction"),
+fn parse_with_color_swap(line: &str) -> Result<lagoon::DigStep, ParseError> {
+    let (_, color) = parse::split_once(line, "(#")?;
+    let s = parse::strip_suffix(color, ")")?;
+    let Some(last) = s.char_indices().last().map(|(index, _)| index) else {
+        return Err(ParseError::new("a color", s));
+    };
+    let distance = u64::from_str_radix(&s[..last], 16)
+        .map_err(|_| ParseError::new("a hexadecimal distance", &s[..last]))?;
+    let direction = match &s[last..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        d => return Err(ParseError::new("a direction digit 0-3", d)),
     };
-    lagoon::DigStep {
+    Ok(lagoon::DigStep {
         direction,
         distance,
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -57,7 +70,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(67891, part_1(INPUT));
+        if let Some(input) = input::puzzle(18) {
+            assert_eq!(67891, part_1(&input));
+        }
     }
 
     #[test]
@@ -67,11 +82,23 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(94_116_351_948_493, part_2(INPUT));
+        if let Some(input) = input::puzzle(18) {
+            assert_eq!(94_116_351_948_493, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn retraced_trench() {
+        assert_eq!(5, part_1("R 4 (#000000)\nL 4 (#000000)"));
+        assert_eq!(0, part_1(""));
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(18) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
@@ -3,38 +3,56 @@ use std::str::FromStr;
 pub(crate) use part::*;
 pub(crate) use workflow::*;
 
+use crate::parse::{self, ParseError};
+
 mod part;
 mod workflow;
 
-#[must_use]
-pub fn part_1(input: &str) -> u64 {
-    let (workflows, parts) = input.split_once("\n\n").unwrap();
-    let Ok(workflows) = Workflows::from_str(workflows);
-    parts
+pub(crate) fn parse_input(input: &str) -> Result<(Workflows, Vec<Part>), ParseError> {
+    let (workflows, parts) = parse::split_once(input, "\n\n").map_err(|e| e.locate(input))?;
+    let workflows = parse::from_str(workflows).map_err(|e| e.locate(input))?;
+    let parts = parts
         .lines()
-        .map(|line| Part::from_str(line).unwrap())
+        .map(Part::from_str)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.locate(input))?;
+    Ok((workflows, parts))
+}
+
+pub fn try_part_1(input: &str) -> Result<u64, ParseError> {
+    let (workflows, parts) = parse_input(input)?;
+    Ok(parts
+        .into_iter()
         .filter(|&part| workflows.accepts(part))
         .map(|part| part.rating())
-        .sum()
+        .sum())
+}
+
+pub fn try_part_2(input: &str) -> Result<u64, ParseError> {
+    let (workflows, _) = parse::split_once(input, "\n\n").map_err(|e| e.locate(input))?;
+    let workflows: Workflows = parse::from_str(workflows).map_err(|e| e.locate(input))?;
+    Ok(workflows.possible_accepted_parts())
+}
+
+#[must_use]
+pub fn part_1(input: &str) -> u64 {
+    try_part_1(input).unwrap()
 }
 
 #[must_use]
 pub fn part_2(input: &str) -> u64 {
-    let (workflows, _) = input.split_once("\n\n").unwrap();
-    let Ok(workflows) = Workflows::from_str(workflows);
-    workflows.possible_accepted_parts()
+    try_part_2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     extern crate test;
 
-    use test::{black_box, Bencher};
-
     use super::*;
+    use crate::input;
 
     const EXAMPLE: &str = include_str!("example.txt");
-    const INPUT: &str = include_str!("input.txt");
 
     #[test]
     fn example_1() {
@@ -43,7 +61,9 @@ mod tests {
 
     #[test]
     fn answer_1() {
-        assert_eq!(397_134, part_1(INPUT));
+        if let Some(input) = input::puzzle(19) {
+            assert_eq!(397_134, part_1(&input));
+        }
     }
 
     #[test]
@@ -53,11 +73,28 @@ mod tests {
 
     #[test]
     fn answer_2() {
-        assert_eq!(127_517_902_575_337, part_2(INPUT));
+        if let Some(input) = input::puzzle(19) {
+            assert_eq!(127_517_902_575_337, part_2(&input));
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        let first_line_removed = EXAMPLE.split_once('\n').unwrap().1;
+        let error = try_part_1(first_line_removed).unwrap_err();
+        assert_eq!("a known workflow", error.expected());
+        assert_eq!("px", error.found());
+        let error = try_part_2("in{x>10:A}\n\n").unwrap_err();
+        assert_eq!("a final rule without a condition", error.expected());
+        assert!(try_part_2("px{R}\n\n").is_err());
     }
 
+    #[cfg(feature = "nightly")]
     #[bench]
-    fn bench(b: &mut Bencher) {
-        b.iter(|| black_box(part_2(black_box(INPUT))));
+    fn bench(b: &mut test::Bencher) {
+        let Some(input) = input::puzzle(19) else {
+            return;
+        };
+        b.iter(|| test::black_box(part_2(test::black_box(&input))));
     }
 }
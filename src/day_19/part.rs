@@ -2,6 +2,8 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug, Copy, Clone)]
 pub struct Part {
     x: u64,
@@ -67,7 +69,7 @@ impl PartRange {
         }
     }
 
-    pub fn get_mut(&mut self, category: Category) -> &mut Range {
+    pub const fn get_mut(&mut self, category: Category) -> &mut Range {
         match category {
             Category::ExtremeLyCoolLooking => &mut self.x,
             Category::Musical => &mut self.m,
@@ -87,34 +89,34 @@ impl Range {
     }
 }
 
-impl From<char> for Category {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Category {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            'x' => Self::ExtremeLyCoolLooking,
-            'm' => Self::Musical,
-            'a' => Self::Aerodynamic,
-            's' => Self::Shiny,
-            _ => panic!("Unknown category: {c}"),
+            'x' => Ok(Self::ExtremeLyCoolLooking),
+            'm' => Ok(Self::Musical),
+            'a' => Ok(Self::Aerodynamic),
+            's' => Ok(Self::Shiny),
+            _ => Err(ParseError::char("a category", c)),
         }
     }
 }
 
 impl FromStr for Part {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, m, a, s) = s
-            .strip_prefix('{')
-            .unwrap()
-            .strip_suffix('}')
-            .unwrap()
+        let (x, m, a, s) = parse::strip_suffix(parse::strip_prefix(s, "{")?, "}")?
             .split(',')
             .map(|c| {
-                let (_, val) = c.split_once('=').unwrap();
-                u64::from_str(val).unwrap()
+                let (_, val) = parse::split_once(c, "=")?;
+                parse::number(val)
             })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .collect_tuple()
-            .unwrap();
+            .ok_or_else(|| ParseError::new("four ratings", s))?;
         Ok(Self { x, m, a, s })
     }
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use super::{Category, Part, PartRange};
+use crate::parse::{self, ParseError};
 
 #[derive(Debug)]
 struct Workflow {
@@ -54,7 +55,7 @@ impl Workflow {
 impl Rule {
     fn evaluate(&self, part: Part) -> bool {
         self.condition
-            .map_or(true, |condition| condition.evaluate(part))
+            .is_none_or(|condition| condition.evaluate(part))
     }
 }
 
@@ -67,7 +68,7 @@ impl Condition {
         }
     }
 
-    fn evaluate_range(self, parts: PartRange) -> (Option<PartRange>, Option<PartRange>) {
+    const fn evaluate_range(self, parts: PartRange) -> (Option<PartRange>, Option<PartRange>) {
         match self.operator {
             Operator::MoreThan => {
                 let val = parts.get(self.category);
@@ -105,7 +106,7 @@ impl Workflows {
     pub fn accepts(&self, part: Part) -> bool {
         let mut workflow = "in";
         loop {
-            match self.list.get(workflow).unwrap().evaluate(part) {
+            match self.list[workflow].evaluate(part) {
                 RuleResult::Workflow(next_workflow) => workflow = next_workflow.as_str(),
                 RuleResult::Accept => return true,
                 RuleResult::Reject => return false,
@@ -117,7 +118,7 @@ impl Workflows {
         let mut accepted = 0;
         let mut todo = vec![(PartRange::new(1, 4000), "in")];
         while let Some((mut range, workflow)) = todo.pop() {
-            let workflow = self.list.get(workflow).unwrap();
+            let workflow = &self.list[workflow];
             for rule in &workflow.rules {
                 let (a, b) = rule.condition.map_or((Some(range), None), |condition| {
                     condition.evaluate_range(range)
@@ -126,7 +127,7 @@ impl Workflows {
                     match &rule.result {
                         RuleResult::Accept => accepted += a.size(),
                         RuleResult::Reject => {}
-                        RuleResult::Workflow(name) => todo.push((a, &name)),
+                        RuleResult::Workflow(name) => todo.push((a, name)),
                     }
                 }
                 if let Some(b) = b {
@@ -142,31 +143,40 @@ impl Workflows {
 
 // PARSING //
 
-impl From<char> for Operator {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Operator {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, ParseError> {
         match c {
-            '>' => Self::MoreThan,
-            '<' => Self::LessThan,
-            _ => panic!("Unknown operator: {c}"),
+            '>' => Ok(Self::MoreThan),
+            '<' => Ok(Self::LessThan),
+            _ => Err(ParseError::char("'>' or '<'", c)),
         }
     }
 }
 
 impl FromStr for Workflow {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let (name, rules) = s.strip_suffix('}').unwrap().split_once('{').unwrap();
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (name, rules) = parse::split_once(parse::strip_suffix(s, "}")?, "{")?;
         let name = name.to_string();
-        let rules = rules.split(',').map(|rule| rule.parse().unwrap()).collect();
+        let rules = rules
+            .split(',')
+            .map(Rule::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if rules.last().is_none_or(|rule| rule.condition.is_some()) {
+            let last = s.rsplit([',', '{']).next().unwrap_or(s);
+            return Err(ParseError::new("a final rule without a condition", last));
+        }
         Ok(Self { name, rules })
     }
 }
 
 impl FromStr for Rule {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         if let Some((condition, result)) = s.split_once(':') {
             let condition = Some(condition.parse()?);
             let result = result.parse()?;
@@ -180,22 +190,29 @@ impl FromStr for Rule {
 }
 
 impl FromStr for Condition {
-    type Err = !;
-
-    fn from_str(s: &str) -> Result<Self, !> {
-        let mut chars = s.chars();
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut chars = s.char_indices();
+        let mut next = || {
+            chars
+                .next()
+                .ok_or_else(|| ParseError::new("a condition", s))
+        };
+        let (_, category) = next()?;
+        let (index, operator) = next()?;
         Ok(Self {
-            category: chars.next().unwrap().into(),
-            operator: chars.next().unwrap().into(),
-            value: chars.as_str().parse().unwrap(),
+            category: Category::try_from(category).map_err(|e| e.at(s))?,
+            operator: Operator::try_from(operator).map_err(|e| e.at(&s[index..]))?,
+            value: parse::number(chars.as_str())?,
         })
     }
 }
 
 impl FromStr for RuleResult {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         match s {
             "A" => Ok(Self::Accept),
             "R" => Ok(Self::Reject),
@@ -205,14 +222,28 @@ impl FromStr for RuleResult {
 }
 
 impl FromStr for Workflows {
-    type Err = !;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        let list = s
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let list: HashMap<_, _> = s
             .lines()
-            .map(|line| line.parse().unwrap())
-            .map(|workflow: Workflow| (workflow.name.clone(), workflow))
-            .collect();
+            .map(|line| {
+                line.parse()
+                    .map(|workflow: Workflow| (workflow.name.clone(), workflow))
+            })
+            .collect::<Result<_, _>>()?;
+        if !list.contains_key("in") {
+            return Err(ParseError::new("a workflow named \"in\"", s));
+        }
+        // Every rule ends with its result, after the condition if there is one.
+        if let Some(missing) = s
+            .lines()
+            .flat_map(|line| line.split(['{', ',', '}']).skip(1))
+            .filter_map(|rule| rule.rsplit(':').next())
+            .find(|&result| !matches!(result, "A" | "R" | "") && !list.contains_key(result))
+        {
+            return Err(ParseError::new("a known workflow", missing));
+        }
 
         Ok(Self { list })
     }
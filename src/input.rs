@@ -0,0 +1,266 @@
+use std::error::Error;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::{env, fmt, fs, io};
+
+/// Environment variable overriding the directory that puzzle inputs are read from.
+pub const DIRECTORY_VAR: &str = "AOC_INPUTS";
+/// Environment variable holding the adventofcode.com session cookie used to download inputs.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const URL: &str = "https://adventofcode.com/2023";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+        source: io::Error,
+    },
+    Fetch {
+        day: u8,
+        url: String,
+        message: String,
+    },
+    Cache {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+/// A source of puzzle inputs.
+pub trait Provider {
+    fn input(&self, day: u8) -> Result<String, InputError>;
+}
+
+/// Inputs stored as `day_XX.txt` files in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory(pub PathBuf);
+
+/// Downloads inputs from an Advent of Code server, authenticated by a session cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Http {
+    url: String,
+    session: String,
+}
+
+/// Reads inputs from a directory, filling it from another provider on first use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache<P> {
+    directory: Directory,
+    fallback: P,
+}
+
+impl Provider for Directory {
+    fn input(&self, day: u8) -> Result<String, InputError> {
+        read(&self.0, day)
+    }
+}
+
+impl Http {
+    #[must_use]
+    pub fn new(url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            session: session.into(),
+        }
+    }
+
+    /// A fetcher for adventofcode.com, if a session token is set in `AOC_SESSION`.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let session = env::var(SESSION_VAR).ok()?;
+        Some(Self::new(URL, session.trim()))
+    }
+}
+
+impl Provider for Http {
+    fn input(&self, day: u8) -> Result<String, InputError> {
+        let url = format!("{}/day/{day}/input", self.url);
+        let error = |message: String| InputError::Fetch {
+            day,
+            url: url.clone(),
+            message,
+        };
+        ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set(
+                "User-Agent",
+                concat!("adventofcode-2023/", env!("CARGO_PKG_VERSION")),
+            )
+            .call()
+            .map_err(|e| error(e.to_string()))?
+            .into_string()
+            .map_err(|e| error(e.to_string()))
+    }
+}
+
+impl<P: Provider> Cache<P> {
+    pub const fn new(directory: PathBuf, fallback: P) -> Self {
+        Self {
+            directory: Directory(directory),
+            fallback,
+        }
+    }
+}
+
+impl<P: Provider> Provider for Cache<P> {
+    fn input(&self, day: u8) -> Result<String, InputError> {
+        match self.directory.input(day) {
+            Err(InputError::Missing { source, .. }) if source.kind() == ErrorKind::NotFound => {
+                let input = self.fallback.input(day)?;
+                let path = path(&self.directory.0, day);
+                fs::create_dir_all(&self.directory.0)
+                    .and_then(|()| fs::write(&path, &input))
+                    .map_err(|source| InputError::Cache { path, source })?;
+                Ok(input)
+            }
+            result => result,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { day, path, source } => write!(
+                f,
+                "Failed to read the input for day {day} from {}: {source} \
+                 (set {DIRECTORY_VAR} or pass --inputs to read from another directory, \
+                 or set {SESSION_VAR} to download it)",
+                path.display()
+            ),
+            Self::Fetch { day, url, message } => {
+                write!(
+                    f,
+                    "Failed to download the input for day {day} from {url}: {message}"
+                )
+            }
+            Self::Cache { path, source } => {
+                write!(
+                    f,
+                    "Failed to cache the input in {}: {source}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Missing { source, .. } | Self::Cache { source, .. } => Some(source),
+            Self::Fetch { .. } => None,
+        }
+    }
+}
+
+/// The directory named by `AOC_INPUTS`, or `inputs/` in the crate root.
+#[must_use]
+pub fn directory() -> PathBuf {
+    env::var_os(DIRECTORY_VAR).map_or_else(
+        || Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"),
+        PathBuf::from,
+    )
+}
+
+#[must_use]
+pub fn path(directory: &Path, day: u8) -> PathBuf {
+    directory.join(format!("day_{day:02}.txt"))
+}
+
+pub fn read(directory: &Path, day: u8) -> Result<String, InputError> {
+    let path = path(directory, day);
+    fs::read_to_string(&path).map_err(|source| InputError::Missing { day, path, source })
+}
+
+/// The puzzle input for a day's answer tests and benchmarks, which are skipped without one.
+#[cfg(test)]
+pub(crate) fn puzzle(day: u8) -> Option<String> {
+    read(&directory(), day)
+        .inspect_err(|e| eprintln!("Skipping: {e}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    /// Serves a single request with the given status and body, returning the request line and
+    /// headers once the response is sent.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request.join("\n")
+        });
+        (url, server)
+    }
+
+    fn temporary_directory(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc-2023-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn missing() {
+        let error = read(Path::new("no/such/directory"), 5).unwrap_err();
+        assert!(matches!(
+            &error,
+            InputError::Missing { day: 5, path, .. } if path == Path::new("no/such/directory/day_05.txt")
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read the input for day 5 from no/such/directory/day_05.txt"));
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let (url, server) = serve_once("200 OK", "1 2 3\n");
+        let directory = temporary_directory("cache");
+        let cache = Cache::new(directory.clone(), Http::new(url, "secret"));
+        assert_eq!("1 2 3\n", cache.input(7).unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /day/7/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret"));
+        assert_eq!(
+            "1 2 3\n",
+            fs::read_to_string(directory.join("day_07.txt")).unwrap()
+        );
+        // The server is gone, so this must come from the cache.
+        assert_eq!("1 2 3\n", cache.input(7).unwrap());
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn fetch_error() {
+        let (url, server) = serve_once("400 Bad Request", "Please log in.");
+        let directory = temporary_directory("error");
+        let cache = Cache::new(directory.clone(), Http::new(url, "expired"));
+        let error = cache.input(3).unwrap_err();
+        server.join().unwrap();
+        assert!(matches!(error, InputError::Fetch { day: 3, .. }));
+        assert!(!directory.join("day_03.txt").exists());
+    }
+}
@@ -1,13 +1,11 @@
-#![feature(
-    never_type,
-    exhaustive_patterns,
-    test,
-    let_chains,
-    exact_size_is_empty,
-    assert_matches
-)]
+#![cfg_attr(all(test, feature = "nightly"), feature(test))]
 
-pub(crate) mod util;
+pub mod answers;
+pub mod benchmark;
+pub mod input;
+pub mod parse;
+pub mod solver;
+pub mod util;
 
 pub mod day_01;
 pub mod day_02;
@@ -1,10 +1,465 @@
-use adventofcode_2023::day_12;
-use std::time::Instant;
+use adventofcode_2023::answers::{Answers, Verdict};
+use adventofcode_2023::benchmark::{self, Report, Settings};
+use adventofcode_2023::day_14::animation::{self, Animation};
+use adventofcode_2023::day_14::dish::{self, Platform};
+use adventofcode_2023::input::{self, Cache, Provider};
+use adventofcode_2023::solver::{self, Part, Solver};
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use std::{env, fs, io, thread};
 
-const INPUT: &str = include_str!("day_12/input.txt");
+const USAGE: &str = "Usage:
+  adventofcode-2023 [run] --day <N> [--part <1|2>] [--input <PATH|->] [--inputs <DIR>]
+  adventofcode-2023 [run] --all [--concurrent] [--part <1|2>] [--inputs <DIR>]
+  adventofcode-2023 verify [--answers <PATH>] [--inputs <DIR>]
+  adventofcode-2023 bench [--day <N>] [--warm-up <N>] [--iterations <N>] [--format <markdown|json>] [--inputs <DIR>]
+      [--save <PATH>] [--baseline <PATH> [--threshold <PERCENT>]]
+  adventofcode-2023 animate [--format <ascii|svg>] [--delay <MS>] [--input <PATH|->] [--inputs <DIR>]
 
-fn main() {
+The animate command plays day 14's spin cycles up to the first repeated state, or writes
+them as an animated SVG.
+
+Inputs are read from <DIR>/day_XX.txt, where <DIR> defaults to $AOC_INPUTS or inputs/.
+When running a day whose input is missing, it is downloaded into <DIR> if $AOC_SESSION
+holds an adventofcode.com session token. With --concurrent, every day runs on its own thread.";
+
+#[derive(Debug)]
+struct Args {
+    /// The day to run, or `None` for all of them.
+    day: Option<u8>,
+    concurrent: bool,
+    parts: Vec<Part>,
+    input: Option<String>,
+    inputs: PathBuf,
+}
+
+#[derive(Debug)]
+struct VerifyArgs {
+    answers: PathBuf,
+    inputs: PathBuf,
+}
+
+#[derive(Debug)]
+struct BenchArgs {
+    day: Option<u8>,
+    settings: Settings,
+    format: Format,
+    inputs: PathBuf,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+#[derive(Debug)]
+struct AnimateArgs {
+    format: AnimationFormat,
+    delay: u64,
+    input: Option<String>,
+    inputs: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AnimationFormat {
+    Ascii,
+    Svg,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("run") => solve(&Args::parse(args.skip(1))?),
+        Some("verify") => verify(&VerifyArgs::parse(args.skip(1))?),
+        Some("bench") => bench(&BenchArgs::parse(args.skip(1))?),
+        Some("animate") => animate(&AnimateArgs::parse(args.skip(1))?),
+        _ => solve(&Args::parse(args)?),
+    }
+}
+
+fn solve(args: &Args) -> Result<(), String> {
+    let Some(day) = args.day else {
+        return solve_all(args);
+    };
+    let solver = solver::solver(day).ok_or_else(|| format!("Day {day} is not solved yet"))?;
+    let input = read_input(day, args.input.as_deref(), &args.inputs)?;
+    print!("{}", solve_day(solver, &input, &args.parts)?);
+    Ok(())
+}
+
+fn solve_all(args: &Args) -> Result<(), String> {
+    let run = |solver: &dyn Solver| {
+        read_input(solver.day(), None, &args.inputs)
+            .and_then(|input| solve_day(solver, &input, &args.parts))
+            .map_err(|e| format!("Day {}: {e}", solver.day()))
+    };
     let now = Instant::now();
-    let _ = day_12::part_2(INPUT);
-    println!("{} ms", now.elapsed().as_millis());
+    let results: Vec<_> = if args.concurrent {
+        thread::scope(|scope| {
+            let mut handles = Vec::new();
+            for solver in solver::solvers() {
+                handles.push(scope.spawn(move || run(solver)));
+            }
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    } else {
+        solver::solvers().map(run).collect()
+    };
+    let elapsed = now.elapsed();
+    let mut failures = 0;
+    for result in results {
+        match result {
+            Ok(output) => print!("{output}"),
+            Err(message) => {
+                eprintln!("{message}");
+                failures += 1;
+            }
+        }
+    }
+    println!("Total: {} ms", elapsed.as_millis());
+    if failures > 0 {
+        return Err(format!("{failures} day(s) failed"));
+    }
+    Ok(())
+}
+
+/// Solves the parts of a day, returning the answers and timings to print.
+fn solve_day(solver: &dyn Solver, input: &str, parts: &[Part]) -> Result<String, String> {
+    let mut output = String::new();
+    for &part in parts {
+        let now = Instant::now();
+        let answer = solver.solve(part, input).map_err(|e| e.to_string())?;
+        let elapsed = now.elapsed();
+        writeln!(output, "Day {} part {part}: {answer}", solver.day()).unwrap();
+        writeln!(output, "{} ms", elapsed.as_millis()).unwrap();
+    }
+    Ok(output)
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), String> {
+    let answers: Answers = fs::read_to_string(&args.answers)
+        .map_err(|e| format!("Failed to read {}: {e}", args.answers.display()))?
+        .parse()
+        .map_err(|e| format!("Invalid answers file {}: {e}", args.answers.display()))?;
+    let mut failures = 0;
+    println!("Day  Part  Status    Answer                Expected");
+    for solver in solver::solvers() {
+        let day = solver.day();
+        let input = input::read(&args.inputs, day);
+        for part in Part::ALL {
+            let expected = answers.get(day, part).unwrap_or("-");
+            let (status, answer) = match input.as_deref().map(|input| solver.solve(part, input)) {
+                Err(_) => (
+                    "no input",
+                    input::path(&args.inputs, day).display().to_string(),
+                ),
+                Ok(Err(e)) => ("error", e.to_string()),
+                Ok(Ok(answer)) => match answers.check(day, part, answer) {
+                    Verdict::Pass => ("pass", answer.to_string()),
+                    Verdict::Fail { .. } => ("FAIL", answer.to_string()),
+                    Verdict::Missing => ("missing", answer.to_string()),
+                },
+            };
+            if matches!(status, "FAIL" | "error") {
+                failures += 1;
+            }
+            println!("{day:>3}  {part:>4}  {status:<8}  {answer:<20}  {expected}");
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{failures} answer(s) did not verify"));
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs) -> Result<(), String> {
+    if let Some(day) = args.day.filter(|&day| solver::solver(day).is_none()) {
+        return Err(format!("Day {day} is not solved yet"));
+    }
+    let mut days = Vec::new();
+    for solver in solver::solvers().filter(|solver| args.day.is_none_or(|day| day == solver.day()))
+    {
+        let input = match input::read(&args.inputs, solver.day()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Skipping day {}: {e}", solver.day());
+                continue;
+            }
+        };
+        let report = benchmark::day(solver, &input, args.settings)
+            .map_err(|e| format!("Day {}: {e}", solver.day()))?;
+        days.push(report);
+    }
+    let report = Report::new(args.settings, days);
+    match args.format {
+        Format::Markdown => print!("{}", report.to_markdown()),
+        Format::Json => println!("{}", report.to_json()),
+    }
+    if let Some(path) = &args.save {
+        fs::write(path, report.to_json())
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    }
+    if let Some(path) = &args.baseline {
+        let baseline = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+            .and_then(|json| Report::from_json(&json))
+            .map_err(|e| format!("Invalid baseline {}: {e}", path.display()))?;
+        let comparisons = benchmark::compare(&baseline, &report, args.threshold / 100.0);
+        println!(
+            "\nCompared with {}:\n\n{}",
+            path.display(),
+            benchmark::comparison_to_markdown(&comparisons)
+        );
+        let regressions = comparisons.iter().filter(|c| c.regressed).count();
+        if regressions > 0 {
+            return Err(format!(
+                "{regressions} timing(s) more than {}% slower than the baseline",
+                args.threshold
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn animate(args: &AnimateArgs) -> Result<(), String> {
+    let platform: Platform = read_input(14, args.input.as_deref(), &args.inputs)?
+        .parse()
+        .map_err(|e| format!("Invalid input: {e}"))?;
+    let animation = Animation::record(platform, &dish::SPIN_CYCLE);
+    if matches!(args.format, AnimationFormat::Svg) {
+        print!("{}", animation.to_svg(args.delay));
+        return Ok(());
+    }
+    let mut stdout = io::stdout().lock();
+    for (index, frame) in animation.frames.iter().enumerate() {
+        writeln!(
+            stdout,
+            "{}{}{}",
+            animation::CLEAR,
+            frame.platform,
+            animation.caption(index)
+        )
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("Failed to write the animation: {e}"))?;
+        thread::sleep(Duration::from_millis(args.delay));
+    }
+    Ok(())
+}
+
+impl AnimateArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut format = AnimationFormat::Ascii;
+        let mut delay = 100;
+        let mut input = None;
+        let mut inputs = input::directory();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--format" | "-f" => {
+                    format = match value("--format")?.as_str() {
+                        "ascii" => AnimationFormat::Ascii,
+                        "svg" => AnimationFormat::Svg,
+                        other => return Err(format!("Unknown format {other:?}\n{USAGE}")),
+                    };
+                }
+                "--delay" => {
+                    let value = value("--delay")?;
+                    delay = value
+                        .parse()
+                        .map_err(|_| format!("Invalid delay {value:?}, expected milliseconds"))?;
+                }
+                "--input" | "-i" => input = Some(value("--input")?),
+                "--inputs" => inputs = value("--inputs")?.into(),
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                _ => return Err(format!("Unknown argument: {arg}\n{USAGE}")),
+            }
+        }
+        Ok(Self {
+            format,
+            delay,
+            input,
+            inputs,
+        })
+    }
+}
+
+impl BenchArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut day = None;
+        let mut settings = Settings::default();
+        let mut format = Format::Markdown;
+        let mut inputs = input::directory();
+        let mut save = None;
+        let mut baseline = None;
+        let mut threshold = 10.0;
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--day" | "-d" => day = Some(parse_number(&value("--day")?, 1..=25)?),
+                "--save" => save = Some(value("--save")?.into()),
+                "--baseline" => baseline = Some(value("--baseline")?.into()),
+                "--threshold" => {
+                    let value = value("--threshold")?;
+                    threshold = value
+                        .parse()
+                        .ok()
+                        .filter(|threshold: &f64| *threshold >= 0.0)
+                        .ok_or_else(|| {
+                            format!("Invalid threshold {value:?}, expected a percentage")
+                        })?;
+                }
+                "--warm-up" => settings.warm_up = parse_count(&value("--warm-up")?)?,
+                "--iterations" | "-n" => {
+                    settings.iterations = parse_count(&value("--iterations")?)?.max(1);
+                }
+                "--format" | "-f" => {
+                    format = match value("--format")?.as_str() {
+                        "markdown" | "md" => Format::Markdown,
+                        "json" => Format::Json,
+                        other => return Err(format!("Unknown format {other:?}\n{USAGE}")),
+                    };
+                }
+                "--inputs" => inputs = value("--inputs")?.into(),
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                _ => return Err(format!("Unknown argument: {arg}\n{USAGE}")),
+            }
+        }
+        Ok(Self {
+            day,
+            settings,
+            format,
+            inputs,
+            save,
+            baseline,
+            threshold,
+        })
+    }
+}
+
+impl VerifyArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut answers = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml"));
+        let mut inputs = input::directory();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--answers" | "-a" => answers = value("--answers")?.into(),
+                "--inputs" => inputs = value("--inputs")?.into(),
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                _ => return Err(format!("Unknown argument: {arg}\n{USAGE}")),
+            }
+        }
+        Ok(Self { answers, inputs })
+    }
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut day = None;
+        let mut all = false;
+        let mut concurrent = false;
+        let mut parts = Part::ALL.to_vec();
+        let mut input = None;
+        let mut inputs = input::directory();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--day" | "-d" => day = Some(parse_number(&value("--day")?, 1..=25)?),
+                "--part" | "-p" => {
+                    let part = parse_number(&value("--part")?, 1..=2)?;
+                    parts = vec![Part::try_from(part).unwrap()];
+                }
+                "--input" | "-i" => input = Some(value("--input")?),
+                "--inputs" => inputs = value("--inputs")?.into(),
+                "--all" | "-a" => all = true,
+                "--concurrent" => concurrent = true,
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                _ => return Err(format!("Unknown argument: {arg}\n{USAGE}")),
+            }
+        }
+        match (day, all) {
+            (None, false) => return Err(format!("Missing --day or --all\n{USAGE}")),
+            (Some(_), true) => return Err(format!("--day and --all are exclusive\n{USAGE}")),
+            (Some(_), false) if concurrent => {
+                return Err(format!("--concurrent needs --all\n{USAGE}"));
+            }
+            (None, true) if input.is_some() => {
+                return Err(format!("--input needs --day\n{USAGE}"));
+            }
+            _ => {}
+        }
+        Ok(Self {
+            day,
+            concurrent,
+            parts,
+            input,
+            inputs,
+        })
+    }
+}
+
+fn parse_number(s: &str, range: std::ops::RangeInclusive<u8>) -> Result<u8, String> {
+    s.parse().ok().filter(|n| range.contains(n)).ok_or_else(|| {
+        format!(
+            "Invalid number {s:?}, expected {}..={}",
+            range.start(),
+            range.end()
+        )
+    })
+}
+
+fn parse_count(s: &str) -> Result<usize, String> {
+    s.parse()
+        .map_err(|_| format!("Invalid count {s:?}, expected a non-negative integer"))
+}
+
+fn read_input(day: u8, path: Option<&str>, directory: &Path) -> Result<String, String> {
+    match path {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}")),
+        None => input::Http::from_env()
+            .map_or_else(
+                || input::read(directory, day),
+                |http| Cache::new(directory.to_path_buf(), http).input(day),
+            )
+            .map_err(|e| e.to_string()),
+    }
 }
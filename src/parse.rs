@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    expected: String,
+    found: String,
+    address: Option<usize>,
+    location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    /// An error pointing at `found`, which should be a slice of the input being parsed.
+    pub fn new(expected: impl Into<String>, found: &str) -> Self {
+        Self {
+            expected: expected.into(),
+            found: found.to_string(),
+            address: Some(found.as_ptr() as usize),
+            location: None,
+        }
+    }
+
+    /// An error for a single character, which has no position until it is given one with `at`.
+    pub fn char(expected: impl Into<String>, found: char) -> Self {
+        Self {
+            expected: expected.into(),
+            found: found.to_string(),
+            address: None,
+            location: None,
+        }
+    }
+
+    #[must_use]
+    pub fn at(mut self, found: &str) -> Self {
+        self.address.get_or_insert(found.as_ptr() as usize);
+        self
+    }
+
+    /// Resolves the line and column of the error, if it points into `source`.
+    #[must_use]
+    pub fn locate(mut self, source: &str) -> Self {
+        let start = source.as_ptr() as usize;
+        let before = self
+            .address
+            .and_then(|address| address.checked_sub(start))
+            .and_then(|offset| source.get(..offset));
+        if let (None, Some(before)) = (self.location, before) {
+            let line = before.matches('\n').count() + 1;
+            let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+            self.location = Some(Location { line, column });
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    #[must_use]
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+
+    #[must_use]
+    pub const fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(Location { line, column }) = self.location {
+            write!(f, "line {line}, column {column}: ")?;
+        }
+        write!(f, "expected {}, ", self.expected)?;
+        if self.found.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses the whole input, resolving the location of any error within it.
+pub fn from_str<T: FromStr<Err = ParseError>>(source: &str) -> Result<T, ParseError> {
+    source.parse().map_err(|e: ParseError| e.locate(source))
+}
+
+pub fn number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::new("a number", s))
+}
+
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(format!("{delimiter:?}"), s))
+}
+
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(format!("{prefix:?}"), s))
+}
+
+pub fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    s.strip_suffix(suffix)
+        .ok_or_else(|| ParseError::new(format!("{suffix:?}"), s))
+}
+
+/// Converts each character of a line, pointing any error at the offending character.
+pub fn chars<'a, T: 'a>(
+    line: &'a str,
+    f: impl Fn(char) -> Result<T, ParseError> + 'a,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    line.char_indices()
+        .map(move |(index, c)| f(c).map_err(|e| e.at(&line[index..index + c.len_utf8()])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate() {
+        let source = "12 34\n56 7x";
+        let error = source
+            .lines()
+            .flat_map(|line| line.split(' '))
+            .map(number::<u32>)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err()
+            .locate(source);
+        assert_eq!(Some(Location { line: 2, column: 4 }), error.location());
+        assert_eq!(
+            "line 2, column 4: expected a number, found \"7x\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn locate_char() {
+        let source = "ab\ncd";
+        let error = source
+            .lines()
+            .flat_map(|line| {
+                chars(line, |c| match c {
+                    'a'..='c' => Ok(c),
+                    _ => Err(ParseError::char("a, b or c", c)),
+                })
+            })
+            .collect::<Result<String, _>>()
+            .unwrap_err()
+            .locate(source);
+        assert_eq!(Some(Location { line: 2, column: 2 }), error.location());
+    }
+
+    #[test]
+    fn unrelated_source() {
+        let error = ParseError::new("a number", "x").locate(&String::from("12"));
+        assert_eq!(None, error.location());
+        assert_eq!("expected a number, found \"x\"", error.to_string());
+    }
+}
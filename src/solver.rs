@@ -0,0 +1,321 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::ParseError;
+use crate::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+/// Why a day could not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// The input is well formed but has no answer, for the reason given.
+    NoAnswer(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Parses the input once for both parts, for days whose parts share a parsing step.
+    fn parse<'a>(&self, input: &'a str) -> Option<Result<Box<dyn Parsed + 'a>, ParseError>>;
+
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => self.part_1(input),
+            Part::Two => self.part_2(input),
+        }
+    }
+}
+
+/// A day's input, parsed and ready to solve either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<Answer, SolveError>;
+}
+
+type Parser = for<'a> fn(&'a str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+
+struct ParsedInput<'a, T> {
+    input: &'a str,
+    parsed: T,
+    part_1: fn(&T) -> Result<Answer, SolveError>,
+    part_2: fn(&T) -> Result<Answer, SolveError>,
+}
+
+/// The return types of the days' solutions, which are either answers or results of one.
+trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, SolveError>;
+}
+
+pub struct Day {
+    number: u8,
+    part_1: fn(&str) -> Result<Answer, SolveError>,
+    part_2: fn(&str) -> Result<Answer, SolveError>,
+    parse: Option<Parser>,
+}
+
+impl Solver for Day {
+    fn day(&self) -> u8 {
+        self.number
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        (self.part_1)(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        (self.part_2)(input)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Option<Result<Box<dyn Parsed + 'a>, ParseError>> {
+        self.parse.map(|parse| parse(input))
+    }
+}
+
+impl<T> Parsed for ParsedInput<'_, T> {
+    fn solve(&self, part: Part) -> Result<Answer, SolveError> {
+        let solve = match part {
+            Part::One => self.part_1,
+            Part::Two => self.part_2,
+        };
+        solve(&self.parsed).map_err(|e| match e {
+            SolveError::Parse(e) => SolveError::Parse(e.locate(self.input)),
+            e @ SolveError::NoAnswer(_) => e,
+        })
+    }
+}
+
+impl Part {
+    pub const ALL: [Self; 2] = [Self::One, Self::Two];
+
+    #[must_use]
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(number: u8) -> Result<Self, u8> {
+        match number {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => Err(number),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.number().fmt(f)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Unsigned(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(u64::try_from(value).unwrap())
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Self::Signed(value.into())
+    }
+}
+
+macro_rules! into_answer {
+    ($($t:ty),*) => {
+        $(impl IntoAnswer for $t {
+            fn into_answer(self) -> Result<Answer, SolveError> {
+                Ok(self.into())
+            }
+        })*
+    };
+}
+
+into_answer!(u32, u64, usize, i32);
+
+impl<T: IntoAnswer, E: Into<SolveError>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, SolveError> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "Invalid input: {error}"),
+            Self::NoAnswer(reason) => write!(f, "No answer: {reason}"),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(error) => Some(error),
+            Self::NoAnswer(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(value) => value.fmt(f),
+            Self::Signed(value) => value.fmt(f),
+        }
+    }
+}
+
+// REGISTRY //
+
+macro_rules! day {
+    ($number:literal, $day:ident) => {
+        Day {
+            number: $number,
+            part_1: |input| $day::try_part_1(input).into_answer(),
+            part_2: |input| $day::try_part_2(input).into_answer(),
+            parse: None,
+        }
+    };
+    // The parts share `parse_input`, and `solve_1` and `solve_2` take its result.
+    ($number:literal, $day:ident, shared) => {
+        Day {
+            parse: Some(|input| {
+                Ok(Box::new(ParsedInput {
+                    input,
+                    parsed: $day::parse_input(input)?,
+                    part_1: |parsed| $day::solve_1(parsed).into_answer(),
+                    part_2: |parsed| $day::solve_2(parsed).into_answer(),
+                }))
+            }),
+            ..day!($number, $day)
+        }
+    };
+}
+
+static DAYS: [Day; 19] = [
+    day!(1, day_01),
+    day!(2, day_02, shared),
+    day!(3, day_03, shared),
+    day!(4, day_04, shared),
+    day!(5, day_05, shared),
+    day!(6, day_06),
+    day!(7, day_07),
+    day!(8, day_08, shared),
+    day!(9, day_09, shared),
+    day!(10, day_10, shared),
+    day!(11, day_11, shared),
+    day!(12, day_12, shared),
+    day!(13, day_13, shared),
+    day!(14, day_14, shared),
+    day!(15, day_15, shared),
+    day!(16, day_16, shared),
+    day!(17, day_17, shared),
+    day!(18, day_18),
+    day!(19, day_19),
+];
+
+pub fn solvers() -> impl Iterator<Item = &'static dyn Solver> {
+    DAYS.iter().map(|day| day as &dyn Solver)
+}
+
+#[must_use]
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    solvers().find(|solver| solver.day() == day)
+}
+
+#[must_use]
+pub fn get(day: u8, part: Part) -> Option<impl Fn(&str) -> Result<Answer, SolveError>> {
+    let solver = solver(day)?;
+    Some(move |input: &str| solver.solve(part, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        let days: Vec<_> = solvers().map(Solver::day).collect();
+        assert_eq!((1..=19).collect::<Vec<_>>(), days);
+    }
+
+    #[test]
+    fn answers() {
+        let input = include_str!("day_09/example.txt");
+        assert_eq!(
+            Some(Ok(Answer::Signed(114))),
+            get(9, Part::One).map(|f| f(input))
+        );
+        let input = include_str!("day_17/mini_example.txt");
+        assert_eq!(
+            Some(Ok(Answer::Unsigned(71))),
+            get(17, Part::Two).map(|f| f(input))
+        );
+        let error = get(9, Part::One).unwrap()("0 3 6\n1 x 3").unwrap_err();
+        assert_eq!(
+            "Invalid input: line 2, column 3: expected a number, found \"x\"",
+            error.to_string()
+        );
+        assert!(get(20, Part::One).is_none());
+    }
+
+    #[test]
+    fn parsed() {
+        let parsed = solver(9)
+            .unwrap()
+            .parse(include_str!("day_09/example.txt"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(Ok(Answer::Signed(114)), parsed.solve(Part::One));
+        assert_eq!(Ok(Answer::Signed(2)), parsed.solve(Part::Two));
+        let input = "##\n##\n\n#.#\n.#.\n";
+        let parsed = solver(13).unwrap().parse(input).unwrap().unwrap();
+        let Err(SolveError::Parse(error)) = parsed.solve(Part::One) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(4, error.location().unwrap().line);
+        assert!(solver(6).unwrap().parse("Time: 7\nDistance: 9").is_none());
+    }
+}
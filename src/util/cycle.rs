@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence `x, f(x), f(f(x)), ...` starts repeating, and after how many steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The index before the end of the first cycle holding the same state as index `n`.
+    #[must_use]
+    pub const fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle by remembering every state, returning it along with the states up to the
+/// end of the first cycle so that any later state can be looked up by [`Cycle::index`].
+pub fn detect_cycle<S: Clone + Hash + Eq>(
+    initial: S,
+    mut next: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut indices = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = indices.get(&state) {
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+        indices.insert(state.clone(), states.len());
+        let following = next(&state);
+        states.push(state);
+        state = following;
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only a couple of states in memory.
+pub fn brent_cycle<S: Clone + Eq>(initial: S, mut next: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare algorithm.
+pub fn floyd_cycle<S: Clone + Eq>(initial: S, mut next: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = next(&initial);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        hare = next(&hare);
+    }
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+    let mut length = 1;
+    hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles() {
+        // Counts up to 9 and then jumps back to 4.
+        let next = |&n: &u32| if n < 9 { n + 1 } else { 4 };
+        let expected = Cycle {
+            start: 4,
+            length: 6,
+        };
+        let (cycle, states) = detect_cycle(0, next);
+        assert_eq!(expected, cycle);
+        assert_eq!((0..10).collect::<Vec<_>>(), states);
+        assert_eq!(expected, brent_cycle(0, next));
+        assert_eq!(expected, floyd_cycle(0, next));
+        assert_eq!(3, cycle.index(3));
+        assert_eq!(5, cycle.index(11));
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 1
+            },
+            brent_cycle(7, |&n| n)
+        );
+    }
+}
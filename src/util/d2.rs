@@ -1,7 +1,7 @@
-use crate::util::Direction;
-use std::ops::{Add, AddAssign, Mul};
+use crate::util::{Diagonal, Direction, Position};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Default)]
 pub struct Vector2D {
     pub x: i64,
     pub y: i64,
@@ -10,13 +10,36 @@ pub struct Vector2D {
 impl Vector2D {
     pub const ZERO: Self = Self::new(0, 0);
 
+    #[must_use]
     pub const fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
 
+    #[must_use]
     pub const fn cross_product(self, rhs: Self) -> i64 {
         self.x * rhs.y - self.y * rhs.x
     }
+
+    #[must_use]
+    pub const fn dot_product(self, rhs: Self) -> i64 {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    #[must_use]
+    pub const fn manhattan_distance(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[must_use]
+    pub const fn chebyshev_distance(self, other: Self) -> u64 {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
 }
 
 impl Add for Vector2D {
@@ -33,6 +56,28 @@ impl AddAssign for Vector2D {
     }
 }
 
+impl Sub for Vector2D {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vector2D {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vector2D {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
 impl From<Direction> for Vector2D {
     fn from(direction: Direction) -> Self {
         match direction {
@@ -44,6 +89,20 @@ impl From<Direction> for Vector2D {
     }
 }
 
+impl From<Diagonal> for Vector2D {
+    fn from(diagonal: Diagonal) -> Self {
+        let (vertical, horizontal) = diagonal.components();
+        Self::from(vertical) + Self::from(horizontal)
+    }
+}
+
+/// Rows grow downwards while `y` grows upwards, so row `i` maps to `y = -i`.
+impl From<Position> for Vector2D {
+    fn from(Position { i, j }: Position) -> Self {
+        Self::new(i64::try_from(j).unwrap(), -i64::try_from(i).unwrap())
+    }
+}
+
 impl Mul<i64> for Vector2D {
     type Output = Self;
 
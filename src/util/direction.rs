@@ -1,5 +1,6 @@
+use crate::parse::ParseError;
 use crate::util::Orientation;
-use std::mem;
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -10,41 +11,100 @@ pub enum Direction {
     Right,
 }
 
-#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
+/// The directions between two neighbouring `Direction`s, for 8-way movement.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Diagonal {
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DirectionMap<T> {
-    up: Option<T>,
-    down: Option<T>,
-    left: Option<T>,
-    right: Option<T>,
+    values: [Option<T>; 4],
 }
 
+/// A vacant or occupied value of a `DirectionMap`.
+pub struct Entry<'a, T>(&'a mut Option<T>);
+
+/// A set of directions, stored as one bit per direction.
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
 pub struct DirectionSet {
-    map: DirectionMap<()>,
+    bits: u8,
 }
 
 impl Direction {
-    // pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// The position of this direction in `ALL`.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Up => 0,
+            Self::Down => 1,
+            Self::Left => 2,
+            Self::Right => 3,
+        }
+    }
+    #[must_use]
     pub const fn is_horizontal(self) -> bool {
         matches!(self, Self::Left | Self::Right)
     }
+    #[must_use]
     pub const fn is_vertical(self) -> bool {
         matches!(self, Self::Up | Self::Down)
     }
-    // pub const fn opposite(self) -> Self {
-    //     match self {
-    //         Self::Up => Self::Down,
-    //         Self::Down => Self::Up,
-    //         Self::Left => Self::Right,
-    //         Self::Right => Self::Left,
-    //     }
-    // }
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        self.turn_left().opposite()
+    }
+    /// The change in row and column of a step in this direction.
+    #[must_use]
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+        }
+    }
+
+    #[must_use]
+    pub const fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Down => 'v',
+            Self::Left => '<',
+            Self::Right => '>',
+        }
+    }
+    #[must_use]
     pub const fn perpendicular(self) -> [Self; 2] {
         match self {
             Self::Up | Self::Down => [Self::Left, Self::Right],
             Self::Left | Self::Right => [Self::Up, Self::Down],
         }
     }
+    #[must_use]
     pub const fn orientation(self) -> Orientation {
         match self {
             Self::Up | Self::Down => Orientation::Vertical,
@@ -53,44 +113,251 @@ impl Direction {
     }
 }
 
+impl Diagonal {
+    pub const ALL: [Self; 4] = [Self::UpLeft, Self::UpRight, Self::DownLeft, Self::DownRight];
+
+    #[must_use]
+    pub const fn new(vertical: Direction, horizontal: Direction) -> Option<Self> {
+        match (vertical, horizontal) {
+            (Direction::Up, Direction::Left) => Some(Self::UpLeft),
+            (Direction::Up, Direction::Right) => Some(Self::UpRight),
+            (Direction::Down, Direction::Left) => Some(Self::DownLeft),
+            (Direction::Down, Direction::Right) => Some(Self::DownRight),
+            _ => None,
+        }
+    }
+    /// The vertical and horizontal directions combined in this diagonal.
+    #[must_use]
+    pub const fn components(self) -> (Direction, Direction) {
+        match self {
+            Self::UpLeft => (Direction::Up, Direction::Left),
+            Self::UpRight => (Direction::Up, Direction::Right),
+            Self::DownLeft => (Direction::Down, Direction::Left),
+            Self::DownRight => (Direction::Down, Direction::Right),
+        }
+    }
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::UpLeft => Self::DownRight,
+            Self::UpRight => Self::DownLeft,
+            Self::DownLeft => Self::UpRight,
+            Self::DownRight => Self::UpLeft,
+        }
+    }
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Self::UpLeft => Self::DownLeft,
+            Self::DownLeft => Self::DownRight,
+            Self::DownRight => Self::UpRight,
+            Self::UpRight => Self::UpLeft,
+        }
+    }
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        self.turn_left().opposite()
+    }
+    /// The change in row and column of a step in this direction.
+    #[must_use]
+    pub const fn offset(self) -> (isize, isize) {
+        let (vertical, horizontal) = self.components();
+        (vertical.offset().0, horizontal.offset().1)
+    }
+}
+
 impl<T> DirectionMap<T> {
-    // pub const fn get(&self, direction: Direction) -> Option<&T> {
-    //     let val = match direction {
-    //         Direction::Up => &self.up,
-    //         Direction::Down => &self.down,
-    //         Direction::Left => &self.left,
-    //         Direction::Right => &self.right,
-    //     };
-    //     val.as_ref()
-    // }
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: [None, None, None, None],
+        }
+    }
+
+    #[must_use]
+    pub const fn get(&self, direction: Direction) -> Option<&T> {
+        self.values[direction.index()].as_ref()
+    }
+
+    pub const fn get_mut(&mut self, direction: Direction) -> Option<&mut T> {
+        self.values[direction.index()].as_mut()
+    }
+
+    pub const fn insert(&mut self, direction: Direction, value: T) -> Option<T> {
+        self.values[direction.index()].replace(value)
+    }
+
+    pub const fn remove(&mut self, direction: Direction) -> Option<T> {
+        self.values[direction.index()].take()
+    }
+
+    pub const fn entry(&mut self, direction: Direction) -> Entry<'_, T> {
+        Entry(&mut self.values[direction.index()])
+    }
+
+    #[must_use]
+    pub const fn contains_key(&self, direction: Direction) -> bool {
+        self.values[direction.index()].is_some()
+    }
+
+    pub fn keys(&self) -> DirectionSet {
+        self.iter().map(|(direction, _)| direction).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        Direction::ALL
+            .into_iter()
+            .zip(&self.values)
+            .filter_map(|(direction, value)| Some((direction, value.as_ref()?)))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Direction, &mut T)> {
+        Direction::ALL
+            .into_iter()
+            .zip(&mut self.values)
+            .filter_map(|(direction, value)| Some((direction, value.as_mut()?)))
+    }
 
-    pub fn insert(&mut self, direction: Direction, value: T) -> Option<T> {
-        let val = match direction {
-            Direction::Up => &mut self.up,
-            Direction::Down => &mut self.down,
-            Direction::Left => &mut self.left,
-            Direction::Right => &mut self.right,
-        };
-        mem::replace(val, Some(value))
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
     }
 
-    pub const fn is_empty(&self) -> bool {
-        self.up.is_none() && self.down.is_none() && self.left.is_none() && self.right.is_none()
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+}
+
+impl<'a, T> Entry<'a, T> {
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        self.0.get_or_insert(default)
+    }
+
+    pub fn or_insert_with(self, default: impl FnOnce() -> T) -> &'a mut T {
+        self.0.get_or_insert_with(default)
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.0.get_or_insert_with(T::default)
+    }
+
+    #[must_use]
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(value) = self.0.as_mut() {
+            f(value);
+        }
+        self
     }
 }
 
 impl DirectionSet {
-    pub fn insert(&mut self, direction: Direction) -> bool {
-        self.map.insert(direction, ()).is_none()
+    pub const ALL: Self = Self { bits: 0b1111 };
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    const fn bit(direction: Direction) -> u8 {
+        1 << direction.index()
+    }
+
+    /// Adds a direction, returning whether it was not yet present.
+    pub const fn insert(&mut self, direction: Direction) -> bool {
+        let inserted = !self.contains(direction);
+        self.bits |= Self::bit(direction);
+        inserted
     }
 
+    /// Removes a direction, returning whether it was present.
+    pub const fn remove(&mut self, direction: Direction) -> bool {
+        let removed = self.contains(direction);
+        self.bits &= !Self::bit(direction);
+        removed
+    }
+
+    #[must_use]
+    pub const fn contains(self, direction: Direction) -> bool {
+        self.bits & Self::bit(direction) != 0
+    }
+
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    #[must_use]
+    pub const fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    #[must_use]
     pub const fn is_empty(self) -> bool {
-        self.map.is_empty()
+        self.bits == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |&direction| self.contains(direction))
+    }
+}
+
+impl<T> Default for DirectionMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<Direction> for DirectionMap<T> {
+    type Output = T;
+
+    fn index(&self, direction: Direction) -> &T {
+        self.get(direction)
+            .unwrap_or_else(|| panic!("No value for {direction:?}"))
+    }
+}
+
+impl<T> IndexMut<Direction> for DirectionMap<T> {
+    fn index_mut(&mut self, direction: Direction) -> &mut T {
+        self.get_mut(direction)
+            .unwrap_or_else(|| panic!("No value for {direction:?}"))
+    }
+}
+
+impl<T> FromIterator<(Direction, T)> for DirectionMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Direction, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (direction, value) in iter {
+            map.insert(direction, value);
+        }
+        map
+    }
+}
+
+impl FromIterator<Direction> for DirectionSet {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for direction in iter {
+            set.insert(direction);
+        }
+        set
     }
 }
 
 impl FromStr for Direction {
-    type Err = !;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -98,7 +365,72 @@ impl FromStr for Direction {
             "D" => Ok(Self::Down),
             "L" => Ok(Self::Left),
             "R" => Ok(Self::Right),
-            _ => panic!("Invalid direction: {s}"),
+            _ => Err(ParseError::new("U, D, L or R", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_left().turn_left());
+            assert!(direction.perpendicular().contains(&direction.turn_right()));
+        }
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Diagonal::UpRight, Diagonal::UpLeft.turn_right());
+        assert_eq!(
+            Diagonal::DownLeft.opposite(),
+            Diagonal::DownLeft.turn_left().turn_left()
+        );
+    }
+
+    #[test]
+    fn set() {
+        let mut set = DirectionSet::new();
+        assert!(set.insert(Direction::Left));
+        assert!(!set.insert(Direction::Left));
+        assert!(set.insert(Direction::Up));
+        assert_eq!(2, set.len());
+        assert_eq!(
+            vec![Direction::Up, Direction::Left],
+            set.iter().collect::<Vec<_>>()
+        );
+        let other: DirectionSet = [Direction::Down, Direction::Left].into_iter().collect();
+        assert_eq!(DirectionSet::ALL.len() - 1, set.union(other).len());
+        assert!(set.remove(Direction::Left));
+        assert!(!set.contains(Direction::Left));
+        assert!(!set.remove(Direction::Left));
+    }
+
+    #[test]
+    fn map() {
+        let mut map = DirectionMap::new();
+        assert_eq!(None, map.insert(Direction::Right, 1));
+        *map.entry(Direction::Right).or_insert(0) += 10;
+        *map.entry(Direction::Down).or_default() += 5;
+        assert_eq!(11, map[Direction::Right]);
+        assert_eq!(Some(&5), map.get(Direction::Down));
+        assert_eq!(
+            vec![(Direction::Down, &5), (Direction::Right, &11)],
+            map.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(2, map.keys().len());
+        assert_eq!(Some(5), map.remove(Direction::Down));
+        assert_eq!(1, map.len());
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!((-1, 1), Diagonal::UpRight.offset());
+        for diagonal in Diagonal::ALL {
+            let (vertical, horizontal) = diagonal.components();
+            assert_eq!(Some(diagonal), Diagonal::new(vertical, horizontal));
         }
+        assert_eq!(None, Diagonal::new(Direction::Left, Direction::Right));
     }
 }
@@ -0,0 +1,200 @@
+use std::iter;
+
+use crate::util::Vector2D;
+
+/// A closed polygon with integer vertices; the last vertex connects back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Vector2D>,
+}
+
+impl Polygon {
+    #[must_use]
+    pub const fn new(vertices: Vec<Vector2D>) -> Self {
+        Self { vertices }
+    }
+
+    /// The polygon traced by starting at the origin and following each move in turn.
+    pub fn from_moves(moves: impl IntoIterator<Item = Vector2D>) -> Self {
+        let vertices = moves
+            .into_iter()
+            .scan(Vector2D::ZERO, |position, step| {
+                *position += step;
+                Some(*position)
+            })
+            .collect();
+        Self { vertices }
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[Vector2D] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Vector2D, Vector2D)> + '_ {
+        let next = self.vertices.iter().skip(1).chain(self.vertices.first());
+        iter::zip(self.vertices.iter().copied(), next.copied())
+    }
+
+    /// Twice the signed area by the shoelace formula, positive for counterclockwise polygons.
+    #[must_use]
+    pub fn doubled_signed_area(&self) -> i64 {
+        self.edges().map(|(a, b)| a.cross_product(b)).sum()
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn area(&self) -> f64 {
+        self.doubled_signed_area().unsigned_abs() as f64 / 2.0
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let d = b - a;
+                (d.dot_product(d) as f64).sqrt()
+            })
+            .sum()
+    }
+
+    /// The number of lattice points on the edges.
+    #[must_use]
+    pub fn boundary_points(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem; zero for polygons
+    /// without area.
+    #[must_use]
+    pub fn interior_points(&self) -> u64 {
+        let doubled_area = self.doubled_signed_area().unsigned_abs();
+        if doubled_area == 0 {
+            return 0;
+        }
+        (doubled_area + 2).saturating_sub(self.boundary_points()) / 2
+    }
+
+    /// The number of lattice points inside or on the boundary, counting edges that the
+    /// polygon retraces only once.
+    #[must_use]
+    pub fn enclosed_points(&self) -> u64 {
+        if self.vertices.is_empty() {
+            return 0;
+        }
+        u64::midpoint(
+            self.doubled_signed_area().unsigned_abs(),
+            self.boundary_points(),
+        ) + 1
+    }
+
+    #[must_use]
+    pub fn on_boundary(&self, point: Vector2D) -> bool {
+        self.edges().any(|(a, b)| {
+            (b - a).cross_product(point - a) == 0
+                && (a.x.min(b.x)..=a.x.max(b.x)).contains(&point.x)
+                && (a.y.min(b.y)..=a.y.max(b.y)).contains(&point.y)
+        })
+    }
+
+    /// Whether the point lies inside the polygon or on its boundary.
+    #[must_use]
+    pub fn contains(&self, point: Vector2D) -> bool {
+        if self.on_boundary(point) {
+            return true;
+        }
+        let crossings = self
+            .edges()
+            .filter(|&(a, b)| (a.y > point.y) != (b.y > point.y))
+            .filter(|&(a, b)| {
+                // The edge crosses the horizontal line through the point to its right.
+                let side = (b - a).cross_product(point - a);
+                if b.y > a.y {
+                    side > 0
+                } else {
+                    side < 0
+                }
+            })
+            .count();
+        crossings % 2 == 1
+    }
+}
+
+const fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Polygon {
+        Polygon::from_moves([
+            Vector2D::new(4, 0),
+            Vector2D::new(0, 4),
+            Vector2D::new(-4, 0),
+            Vector2D::new(0, -4),
+        ])
+    }
+
+    #[test]
+    fn measures() {
+        let square = square();
+        assert_eq!(32, square.doubled_signed_area());
+        assert!((square.area() - 16.0).abs() < f64::EPSILON);
+        assert!((square.perimeter() - 16.0).abs() < f64::EPSILON);
+        assert_eq!(16, square.boundary_points());
+        assert_eq!(9, square.interior_points());
+        let reversed = Polygon::new(square.vertices().iter().rev().copied().collect());
+        assert_eq!(-32, reversed.doubled_signed_area());
+        let triangle = Polygon::new(vec![
+            Vector2D::ZERO,
+            Vector2D::new(4, 0),
+            Vector2D::new(0, 2),
+        ]);
+        assert_eq!(8, triangle.boundary_points());
+        assert_eq!(1, triangle.interior_points());
+        assert_eq!(9, triangle.enclosed_points());
+    }
+
+    #[test]
+    fn degenerate() {
+        let empty = Polygon::new(Vec::new());
+        assert_eq!(0, empty.interior_points());
+        assert_eq!(0, empty.enclosed_points());
+        let point = Polygon::new(vec![Vector2D::new(1, 1)]);
+        assert_eq!(0, point.interior_points());
+        assert_eq!(1, point.enclosed_points());
+        let line = Polygon::from_moves([Vector2D::new(4, 0), Vector2D::new(-4, 0)]);
+        assert_eq!(0, line.doubled_signed_area());
+        assert_eq!(8, line.boundary_points());
+        assert_eq!(0, line.interior_points());
+        assert_eq!(5, line.enclosed_points());
+        let bent = Polygon::from_moves([
+            Vector2D::new(2, 0),
+            Vector2D::new(0, 2),
+            Vector2D::new(0, -2),
+            Vector2D::new(-2, 0),
+        ]);
+        assert_eq!(0, bent.interior_points());
+        assert_eq!(5, bent.enclosed_points());
+    }
+
+    #[test]
+    fn contains() {
+        let square = square();
+        assert!(square.contains(Vector2D::new(2, 2)));
+        assert!(square.contains(Vector2D::new(4, 1)));
+        assert!(square.on_boundary(Vector2D::new(0, 3)));
+        assert!(!square.on_boundary(Vector2D::new(1, 1)));
+        assert!(!square.contains(Vector2D::new(5, 2)));
+        assert!(!square.contains(Vector2D::new(2, -1)));
+    }
+}
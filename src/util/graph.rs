@@ -0,0 +1,91 @@
+/// The strongly connected components of a graph given as adjacency lists, by Tarjan's
+/// algorithm. Components come in reverse topological order: no component has an edge to a
+/// component after it.
+#[must_use]
+pub fn strongly_connected_components(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut tarjan = Tarjan {
+        indices: vec![None; graph.len()],
+        low_links: vec![0; graph.len()],
+        on_stack: vec![false; graph.len()],
+        stack: Vec::new(),
+        calls: Vec::new(),
+        next_index: 0,
+    };
+    let mut components = Vec::new();
+    for root in 0..graph.len() {
+        if tarjan.indices[root].is_some() {
+            continue;
+        }
+        tarjan.visit(root);
+        while let Some(&(node, edge)) = tarjan.calls.last() {
+            if let Some(&next) = graph[node].get(edge) {
+                tarjan.calls.last_mut().unwrap().1 += 1;
+                match tarjan.indices[next] {
+                    None => tarjan.visit(next),
+                    Some(index) if tarjan.on_stack[next] => {
+                        tarjan.low_links[node] = tarjan.low_links[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            tarjan.calls.pop();
+            if let Some(&(parent, _)) = tarjan.calls.last() {
+                tarjan.low_links[parent] = tarjan.low_links[parent].min(tarjan.low_links[node]);
+            }
+            if Some(tarjan.low_links[node]) == tarjan.indices[node] {
+                components.push(tarjan.pop_component(node));
+            }
+        }
+    }
+    components
+}
+
+struct Tarjan {
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    /// The nodes being visited, with how many of their edges have been followed.
+    calls: Vec<(usize, usize)>,
+    next_index: usize,
+}
+
+impl Tarjan {
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.next_index);
+        self.low_links[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+        self.calls.push((node, 0));
+    }
+
+    fn pop_component(&mut self, root: usize) -> Vec<usize> {
+        let mut component = Vec::new();
+        loop {
+            let node = self.stack.pop().unwrap();
+            self.on_stack[node] = false;
+            component.push(node);
+            if node == root {
+                return component;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        // 0 -> 1 -> 2 -> 0 form a loop leading to 3 <-> 4, and 5 stands alone.
+        let graph = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![]];
+        let mut components = strongly_connected_components(&graph);
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        assert_eq!(vec![vec![3, 4], vec![0, 1, 2], vec![5]], components);
+    }
+}
@@ -1,78 +1,274 @@
-use std::fmt::{Debug, Write};
+use std::fmt::Write;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
-use std::{fmt, slice};
+use std::{fmt, iter, slice};
 
-use crate::util::Position;
+use crate::parse::{self, ParseError};
+use crate::util::{Direction, Position};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Matrix<const M: usize, const N: usize, T> {
-    data: Box<[[T; N]; M]>,
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    height: usize,
+    width: usize,
+    data: Vec<T>,
 }
 
-impl<const M: usize, const N: usize, T: Debug> Matrix<M, N, T> {
-    pub fn from_str_map<F: Fn(char) -> T + Copy>(s: &str, f: F) -> Self {
-        let data = s
-            .lines()
-            .map(|line| line.chars().map(f).collect::<Vec<T>>().try_into().unwrap())
-            .collect::<Vec<[T; N]>>()
-            .try_into()
-            .unwrap();
-        Self { data }
+impl<T> Matrix<T> {
+    #[must_use]
+    pub fn new(height: usize, width: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        Self {
+            height,
+            width,
+            data: vec![T::default(); height * width],
+        }
     }
 
-    pub fn rows(&self) -> slice::Iter<'_, [T; N]> {
-        self.data.iter()
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|i| (0..width).map(move |j| Position::new(i, j)))
+            .map(&mut f)
+            .collect();
+        Self {
+            height,
+            width,
+            data,
+        }
     }
-}
 
-impl<const M: usize, const N: usize, T: Default + Copy> Default for Matrix<M, N, T> {
-    fn default() -> Self {
+    #[must_use]
+    pub fn from_vec(height: usize, width: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            height * width,
+            data.len(),
+            "Data does not fit {height}x{width}"
+        );
         Self {
-            data: Box::new([[T::default(); N]; M]),
+            height,
+            width,
+            data,
         }
     }
+
+    pub fn from_str_map<F>(s: &str, f: F) -> Result<Self, ParseError>
+    where
+        F: Fn(char) -> Result<T, ParseError>,
+    {
+        let mut height = 0;
+        let mut width = 0;
+        let mut data = Vec::with_capacity(s.len());
+        for line in s.lines() {
+            for value in parse::chars(line, &f) {
+                data.push(value?);
+            }
+            if height == 0 {
+                width = data.len();
+            }
+            height += 1;
+            if data.len() != height * width {
+                return Err(ParseError::new(format!("a row of width {width}"), line));
+            }
+        }
+        if data.is_empty() {
+            return Err(ParseError::new("at least one row", s));
+        }
+        Ok(Self {
+            height,
+            width,
+            data,
+        })
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn max_position(&self) -> Position {
+        Position::new(self.height - 1, self.width - 1)
+    }
+
+    #[must_use]
+    pub const fn contains(&self, Position { i, j }: Position) -> bool {
+        i < self.height && j < self.width
+    }
+
+    #[must_use]
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position).then(|| &self[position])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position).then(|| &mut self[position])
+    }
+
+    /// The neighbouring position in the given direction, if it lies within the matrix.
+    #[must_use]
+    pub fn neighbour(&self, position: Position, direction: Direction) -> Option<Position> {
+        position.step(direction).filter(|&p| self.contains(p))
+    }
+
+    /// The horizontally and vertically adjacent positions within the matrix.
+    pub fn neighbours_4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.neighbour(position, direction))
+    }
+
+    /// The adjacent positions within the matrix including diagonals, in row-major order.
+    pub fn neighbours_8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        let Position { i, j } = position;
+        (i.saturating_sub(1)..=i + 1)
+            .flat_map(move |i| (j.saturating_sub(1)..=j + 1).map(move |j| Position::new(i, j)))
+            .filter(move |&p| p != position && self.contains(p))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| Position::new(i, j)))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.data)
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn rows(&self) -> slice::ChunksExact<'_, T> {
+        self.data.chunks_exact(self.width)
+    }
+
+    pub fn column(&self, j: usize) -> iter::StepBy<iter::Skip<slice::Iter<'_, T>>> {
+        assert!(j < self.width, "Column {j} out of bounds");
+        self.data.iter().skip(j).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = iter::StepBy<iter::Skip<slice::Iter<'_, T>>>> {
+        (0..self.width).map(|j| self.column(j))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            height: self.height,
+            width: self.width,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |Position { i, j }| {
+            self[Position::new(j, i)].clone()
+        })
+    }
+
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |Position { i, j }| {
+            self[Position::new(self.height - 1 - j, i)].clone()
+        })
+    }
+
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |Position { i, j }| {
+            self[Position::new(j, self.width - 1 - i)].clone()
+        })
+    }
+
+    /// Mirrors the matrix left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |Position { i, j }| {
+            self[Position::new(i, self.width - 1 - j)].clone()
+        })
+    }
+
+    /// Mirrors the matrix top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |Position { i, j }| {
+            self[Position::new(self.height - 1 - i, j)].clone()
+        })
+    }
 }
 
-impl<const M: usize, const N: usize, T> Index<usize> for Matrix<M, N, T> {
-    type Output = [T; N];
+impl<T> Index<usize> for Matrix<T> {
+    type Output = [T];
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.data[index]
+        &self.data[index * self.width..(index + 1) * self.width]
     }
 }
 
-impl<const M: usize, const N: usize, T> IndexMut<usize> for Matrix<M, N, T> {
+impl<T> IndexMut<usize> for Matrix<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index]
+        &mut self.data[index * self.width..(index + 1) * self.width]
     }
 }
 
-impl<const M: usize, const N: usize, T> Index<Position<M, N>> for Matrix<M, N, T> {
+impl<T> Index<Position> for Matrix<T> {
     type Output = T;
 
-    fn index(&self, Position { i, j }: Position<M, N>) -> &T {
-        &self.data[i][j]
+    fn index(&self, Position { i, j }: Position) -> &T {
+        assert!(j < self.width, "Column {j} out of bounds");
+        &self.data[i * self.width + j]
     }
 }
 
-impl<const M: usize, const N: usize, T> IndexMut<Position<M, N>> for Matrix<M, N, T> {
-    fn index_mut(&mut self, Position { i, j }: Position<M, N>) -> &mut T {
-        &mut self.data[i][j]
+impl<T> IndexMut<Position> for Matrix<T> {
+    fn index_mut(&mut self, Position { i, j }: Position) -> &mut T {
+        assert!(j < self.width, "Column {j} out of bounds");
+        &mut self.data[i * self.width + j]
     }
 }
 
-impl<const M: usize, const N: usize, T: From<char> + Debug> FromStr for Matrix<M, N, T> {
-    type Err = !;
+impl<'a, T> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
 
-    fn from_str(s: &str) -> Result<Self, !> {
-        Ok(Self::from_str_map(s, T::from))
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl<const M: usize, const N: usize, T: fmt::Display> fmt::Display for Matrix<M, N, Option<T>> {
+impl<T: TryFrom<char, Error = ParseError>> FromStr for Matrix<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Self::from_str_map(s, T::try_from)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Matrix<Option<T>> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in self.data.iter() {
+        for row in self.rows() {
             for e in row {
                 if let Some(e) = e {
                     e.fmt(f)?;
@@ -85,3 +281,52 @@ impl<const M: usize, const N: usize, T: fmt::Display> fmt::Display for Matrix<M,
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> Matrix<char> {
+        Matrix::from_str_map("abc\ndef", Ok).unwrap()
+    }
+
+    fn text(matrix: &Matrix<char>) -> Vec<String> {
+        matrix.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn neighbours() {
+        let matrix = matrix();
+        let corner: Vec<_> = matrix.neighbours_4(Position::new(0, 0)).collect();
+        assert_eq!(vec![Position::new(1, 0), Position::new(0, 1)], corner);
+        let middle: Vec<_> = matrix
+            .neighbours_8(Position::new(1, 1))
+            .map(|p| matrix[p])
+            .collect();
+        assert_eq!(vec!['a', 'b', 'c', 'd', 'f'], middle);
+    }
+
+    #[test]
+    fn iteration() {
+        let matrix = matrix();
+        assert_eq!(Some(&'f'), matrix.get(Position::new(1, 2)));
+        assert_eq!(None, matrix.get(Position::new(1, 3)));
+        assert_eq!(Some((Position::new(1, 0), &'d')), matrix.enumerate().nth(3));
+        let columns: Vec<String> = matrix.columns().map(Iterator::collect).collect();
+        assert_eq!(vec!["ad", "be", "cf"], columns);
+    }
+
+    #[test]
+    fn transformations() {
+        let matrix = matrix();
+        assert_eq!(vec!["ad", "be", "cf"], text(&matrix.transpose()));
+        assert_eq!(vec!["da", "eb", "fc"], text(&matrix.rotate_clockwise()));
+        assert_eq!(
+            vec!["cf", "be", "ad"],
+            text(&matrix.rotate_counterclockwise())
+        );
+        assert_eq!(vec!["cba", "fed"], text(&matrix.flip_horizontal()));
+        assert_eq!(vec!["def", "abc"], text(&matrix.flip_vertical()));
+        assert_eq!(matrix, matrix.rotate_clockwise().rotate_counterclockwise());
+    }
+}
@@ -1,11 +1,21 @@
+mod cycle;
 mod d2;
 mod direction;
+mod geometry;
+mod graph;
 mod matrix;
 mod orientation;
+mod parallel;
 mod position;
+mod search;
 
+pub use cycle::*;
 pub use d2::*;
 pub use direction::*;
+pub use geometry::*;
+pub use graph::*;
 pub use matrix::*;
 pub use orientation::*;
+pub use parallel::*;
 pub use position::*;
+pub use search::*;
@@ -0,0 +1,23 @@
+#[cfg(feature = "parallel")]
+use rayon::iter::IntoParallelIterator;
+
+/// The trait whose adapters the iterator from `par_iter` offers, for callers to import.
+#[cfg(feature = "parallel")]
+pub use rayon::iter::ParallelIterator as ParIter;
+
+/// The trait whose adapters the iterator from `par_iter` offers, for callers to import.
+#[cfg(not(feature = "parallel"))]
+pub use std::iter::Iterator as ParIter;
+
+/// Iterates over the items on rayon's thread pool when the `parallel` feature is enabled, and
+/// sequentially otherwise. Callers only use the adapters both kinds of iterator share.
+#[cfg(feature = "parallel")]
+pub fn par_iter<T: IntoParallelIterator>(items: T) -> impl ParIter<Item = T::Item> {
+    items.into_par_iter()
+}
+
+/// Iterates over the items sequentially, as the `parallel` feature is disabled.
+#[cfg(not(feature = "parallel"))]
+pub fn par_iter<T: IntoIterator>(items: T) -> impl Iterator<Item = T::Item> {
+    items.into_iter()
+}
@@ -1,35 +1,33 @@
-use crate::util::Direction;
+use crate::util::{Diagonal, Direction};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
-pub struct Position<const M: usize, const N: usize> {
+pub struct Position {
     pub i: usize,
     pub j: usize,
 }
 
-impl<const M: usize, const N: usize> Position<M, N> {
-    pub const MAX: Self = Self { i: M - 1, j: N - 1 };
-
+impl Position {
+    #[must_use]
     pub const fn new(i: usize, j: usize) -> Self {
         Self { i, j }
     }
 
-    pub fn add(mut self, direction: Direction) -> Option<Self> {
-        match direction {
-            Direction::Up => self.i = self.i.checked_sub(1)?,
-            Direction::Down => {
-                self.i += 1;
-                if self.i >= M {
-                    return None;
-                }
-            }
-            Direction::Left => self.j = self.j.checked_sub(1)?,
-            Direction::Right => {
-                self.j += 1;
-                if self.j >= N {
-                    return None;
-                }
-            }
-        }
-        Some(self)
+    /// Moves one step in the given direction, unless that would go above or left of the origin.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.offset(direction.offset())
+    }
+
+    #[must_use]
+    pub fn step_diagonal(self, diagonal: Diagonal) -> Option<Self> {
+        self.offset(diagonal.offset())
+    }
+
+    #[must_use]
+    pub fn offset(self, (di, dj): (isize, isize)) -> Option<Self> {
+        Some(Self::new(
+            self.i.checked_add_signed(di)?,
+            self.j.checked_add_signed(dj)?,
+        ))
     }
 }
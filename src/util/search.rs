@@ -0,0 +1,119 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest path from any of the start states to a goal state, with its cost.
+///
+/// `neighbours` yields the states reachable from a state along with the cost of each step.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], expanding states in order of their cost plus `heuristic`, which must
+/// never overestimate the remaining cost to a goal.
+pub fn a_star<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // Every state reached so far, with the index of the state it was reached from.
+    let mut nodes: Vec<(S, Option<usize>)> = Vec::new();
+    let mut costs: HashMap<S, (C, usize)> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = costs.entry(start.clone()) {
+            entry.insert((C::default(), nodes.len()));
+            queue.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+            nodes.push((start, None));
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let state = nodes[index].0.clone();
+        if costs[&state].1 != index {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, path(&nodes, index)));
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            let next_index = nodes.len();
+            match costs.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+                Entry::Occupied(mut entry) => entry.insert((next_cost, next_index)),
+                Entry::Vacant(entry) => *entry.insert((next_cost, next_index)),
+            };
+            queue.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                next_index,
+            )));
+            nodes.push((next, Some(index)));
+        }
+    }
+    None
+}
+
+fn path<S: Clone>(nodes: &[(S, Option<usize>)], mut index: usize) -> Vec<S> {
+    let mut path = vec![nodes[index].0.clone()];
+    while let Some(parent) = nodes[index].1 {
+        path.push(nodes[parent].0.clone());
+        index = parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps along a line of numbers, moving by one for a cost of 3 or by two for 5.
+    fn neighbours(n: u32) -> [(u32, u32); 2] {
+        [(n + 1, 3), (n + 2, 5)]
+    }
+
+    #[test]
+    fn shortest_path() {
+        assert_eq!(
+            Some((15, vec![0, 2, 4, 6])),
+            dijkstra([0], |&n| neighbours(n), |&n| n == 6)
+        );
+        assert_eq!(
+            Some((15, vec![0, 2, 4, 6])),
+            a_star(
+                [0],
+                |&n| neighbours(n),
+                |&n| 6u32.saturating_sub(n) * 2,
+                |&n| n == 6
+            )
+        );
+        assert_eq!(
+            Some((0, vec![3])),
+            dijkstra([1, 3], |&n| neighbours(n), |&n| n == 3)
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        let bounded = |&n: &u32| neighbours(n).into_iter().filter(|&(n, _)| n < 10);
+        assert_eq!(None, dijkstra([0], bounded, |&n| n == 10));
+    }
+}